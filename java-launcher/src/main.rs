@@ -0,0 +1,384 @@
+//! A minimal `java` launcher built on the invocation API
+//!
+//! It parses the command line like the JDK's `java` launcher, creates the VM with
+//! `JNI_CreateJavaVM`, calls the `main` method of the main class (or of the `Main-Class` of a
+//! JAR file) and then destroys the VM with `DestroyJavaVM`, which waits for the other
+//! non-daemon threads. The process exits with 0, or with 1 if the launch failed or `main`
+//! threw. A call to `System.exit` exits the process with its status directly.
+//!
+//! The JVM library is found with `JAVA_HOME`, or else from the `java` on the `PATH`, or else
+//! (on Linux) in the newest JDK (by its `release` file) under the usual install roots such as
+//! `/usr/lib/jvm`.
+
+use std::env;
+use std::ffi::{CStr, CString};
+use std::process;
+use std::ptr;
+use std::thread;
+
+use jni_sys::invocation::{destroy_java_vm, detach_current_thread, CreateOptions, InvocationApi};
+use jni_sys::jdk::JdkSearch;
+use jni_sys::options::{JavaVmOptions, Launch, OptionsError, OptionsParser};
+use jni_sys::{
+    jchar, jclass, jmethodID, jobjectArray, jsize, jstring, jvalue, JNIEnv, JNI_FALSE, JNI_TRUE,
+    JNI_VERSION_1_8,
+};
+
+const USAGE: &str = "\
+Usage: java-launcher [options] <mainclass> [args...]
+           (to execute a class)
+   or  java-launcher [options] -jar <jarfile> [args...]
+           (to execute a jar file)
+
+The options are those of the java launcher that are passed to the Java VM. The JVM library
+is found with JAVA_HOME, or else from the java on the PATH, or else (on Linux) under the
+usual JDK install roots such as /usr/lib/jvm.";
+
+/// The options that print the usage
+const HELP_OPTIONS: &[&str] = &["-h", "-?", "-help", "--help"];
+
+/// The stack size of the thread that runs `main`
+///
+/// Like the `java` launcher, the VM isn't created on the process's main thread, whose stack
+/// the VM can't guard.
+const MAIN_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Why launching failed
+enum Failure {
+    /// `main` threw an exception, which has been printed
+    Uncaught,
+    /// The launcher failed, possibly with a pending exception that caused it
+    Error(String),
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Self::Error(message)
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args_os()
+        .skip(1)
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    let launcher = thread::Builder::new()
+        .name("main".to_owned())
+        .stack_size(MAIN_STACK_SIZE)
+        .spawn(move || launch(args))
+        .expect("failed to spawn the main thread");
+    let status = match launcher.join() {
+        Ok(Ok(status)) => status,
+        Ok(Err(message)) => {
+            eprintln!("Error: {message}");
+            1
+        }
+        Err(_) => 1,
+    };
+    process::exit(status);
+}
+
+/// Launches the command line and returns the exit status
+fn launch(args: Vec<String>) -> Result<i32, String> {
+    let parsed = match OptionsParser::from_env().parse(args) {
+        Ok(parsed) => parsed,
+        Err(OptionsError::LauncherOnly(option)) if HELP_OPTIONS.contains(&option.as_str()) => {
+            println!("{USAGE}");
+            return Ok(0);
+        }
+        Err(err) => return Err(err.to_string()),
+    };
+    let Some(launch) = parsed.launch else {
+        eprintln!("{USAGE}");
+        return Ok(1);
+    };
+
+    let mut options = parsed.options;
+    if !options
+        .iter()
+        .any(|option| option.starts_with("-Djava.class.path="))
+    {
+        // The JDK launcher defaults the class path to `CLASSPATH`, or else the current directory
+        let class_path = env::var("CLASSPATH").unwrap_or_else(|_| ".".to_owned());
+        options.push(format!("-Djava.class.path={class_path}"));
+    }
+    let command = match &launch {
+        Launch::MainClass(class) | Launch::Jar(class) => class,
+    };
+    let command = Some(command).into_iter().chain(&parsed.args);
+    options.push(format!(
+        "-Dsun.java.command={}",
+        command.map(String::as_str).collect::<Vec<_>>().join(" ")
+    ));
+    let mut options = JavaVmOptions::new(options)
+        .map_err(|_| "options must not contain NUL characters".to_owned())?;
+
+    let api = load_invocation_api()?;
+    let init_args = options.init_args(JNI_VERSION_1_8, JNI_FALSE);
+    // SAFETY: The API was loaded from a JVM library, and the options outlive the call
+    let created = unsafe { api.create_java_vm(&init_args, CreateOptions::new()) }
+        .map_err(|err| format!("Could not create the Java Virtual Machine: {err}"))?;
+
+    // SAFETY: The env belongs to this thread, which was attached by creating the VM
+    let status = match unsafe { run_main(created.env, &launch, &parsed.args) } {
+        Ok(()) => 0,
+        Err(Failure::Uncaught) => 1,
+        Err(Failure::Error(message)) => {
+            eprintln!("Error: {message}");
+            // SAFETY: As above
+            unsafe { describe_exception(created.env) };
+            1
+        }
+    };
+
+    // SAFETY: The VM was created above, and no references to it outlive it
+    unsafe {
+        // `DestroyJavaVM` waits until this thread is the last non-daemon thread
+        let _ = detach_current_thread(created.vm);
+        let _ = destroy_java_vm(created.vm);
+    }
+    Ok(status)
+}
+
+/// Loads the invocation API from the default JVM library of the first JDK found
+fn load_invocation_api() -> Result<InvocationApi, String> {
+    let libjvm = JdkSearch::from_env()
+        .default_libjvm()
+        .ok_or("no JDK found; set JAVA_HOME to the JDK to launch")?;
+    let path = libjvm
+        .path
+        .to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| format!("unsupported JVM library path {}", libjvm.path.display()))?;
+    // SAFETY: The library is a JVM library, which exports the invocation API
+    unsafe { InvocationApi::load(&path) }
+        .map_err(|err| format!("failed to load {}: {err}", libjvm.path.display()))
+}
+
+/// Calls the `main` method of the launched class with `args`
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn run_main(env: *mut JNIEnv, launch: &Launch, args: &[String]) -> Result<(), Failure> {
+    let class_name = match launch {
+        Launch::MainClass(class) => class.clone(),
+        Launch::Jar(jar) => jar_main_class(env, jar)?,
+    };
+    let binary_name = CString::new(class_name.replace('.', "/"))
+        .map_err(|_| format!("Could not find or load main class {class_name}"))?;
+    let class = ((**env).v1_1.FindClass)(env, binary_name.as_ptr());
+    if class.is_null() {
+        return Err(format!("Could not find or load main class {class_name}").into());
+    }
+    let main = ((**env).v1_1.GetStaticMethodID)(
+        env,
+        class,
+        cstr(b"main\0").as_ptr(),
+        cstr(b"([Ljava/lang/String;)V\0").as_ptr(),
+    );
+    if main.is_null() {
+        // Clear the `NoSuchMethodError`, which the message explains
+        ((**env).v1_1.ExceptionClear)(env);
+        return Err(format!(
+            "Main method not found in class {class_name}, please define the main method as:\n   \
+             public static void main(String[] args)"
+        )
+        .into());
+    }
+
+    let array = string_array(env, args)?;
+    ((**env).v1_1.CallStaticVoidMethodA)(env, class, main, [jvalue { l: array }].as_ptr());
+    if exception_check(env) {
+        ((**env).v1_1.ExceptionDescribe)(env);
+        return Err(Failure::Uncaught);
+    }
+    Ok(())
+}
+
+/// Returns the `Main-Class` in the manifest of `jar`
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn jar_main_class(env: *mut JNIEnv, jar: &str) -> Result<String, Failure> {
+    let error = || Failure::Error(format!("Invalid or corrupt jarfile {jar}"));
+    let jar_file_class = find_class(env, b"java/util/jar/JarFile\0")?;
+    let new = method(env, jar_file_class, b"<init>\0", b"(Ljava/lang/String;)V\0")?;
+    let path = new_string(env, jar)?;
+    let jar_file =
+        ((**env).v1_1.NewObjectA)(env, jar_file_class, new, [jvalue { l: path }].as_ptr());
+    if exception_clear(env) || jar_file.is_null() {
+        return Err(error());
+    }
+
+    let get_manifest = method(
+        env,
+        jar_file_class,
+        b"getManifest\0",
+        b"()Ljava/util/jar/Manifest;\0",
+    )?;
+    let manifest = ((**env).v1_1.CallObjectMethodA)(env, jar_file, get_manifest, ptr::null());
+    // The exception has to be cleared before closing the file, but is reported after
+    let manifest_threw = exception_clear(env);
+    let close = method(env, jar_file_class, b"close\0", b"()V\0")?;
+    ((**env).v1_1.CallVoidMethodA)(env, jar_file, close, ptr::null());
+    if exception_clear(env) || manifest_threw {
+        return Err(error());
+    }
+    let no_main_class = || Failure::Error(format!("no main manifest attribute, in {jar}"));
+    if manifest.is_null() {
+        return Err(no_main_class());
+    }
+
+    let manifest_class = find_class(env, b"java/util/jar/Manifest\0")?;
+    let get_main_attributes = method(
+        env,
+        manifest_class,
+        b"getMainAttributes\0",
+        b"()Ljava/util/jar/Attributes;\0",
+    )?;
+    let attributes =
+        ((**env).v1_1.CallObjectMethodA)(env, manifest, get_main_attributes, ptr::null());
+    if exception_clear(env) || attributes.is_null() {
+        return Err(error());
+    }
+    let attributes_class = find_class(env, b"java/util/jar/Attributes\0")?;
+    let get_value = method(
+        env,
+        attributes_class,
+        b"getValue\0",
+        b"(Ljava/lang/String;)Ljava/lang/String;\0",
+    )?;
+    let name = new_string(env, "Main-Class")?;
+    let main_class: jstring =
+        ((**env).v1_1.CallObjectMethodA)(env, attributes, get_value, [jvalue { l: name }].as_ptr());
+    if exception_clear(env) {
+        return Err(error());
+    }
+    if main_class.is_null() {
+        return Err(no_main_class());
+    }
+    Ok(rust_string(env, main_class).trim().to_owned())
+}
+
+/// Returns a `String[]` holding `strings`
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn string_array(env: *mut JNIEnv, strings: &[String]) -> Result<jobjectArray, Failure> {
+    let string_class = find_class(env, b"java/lang/String\0")?;
+    let len = jsize::try_from(strings.len()).map_err(|_| "too many arguments".to_owned())?;
+    let array = ((**env).v1_1.NewObjectArray)(env, len, string_class, ptr::null_mut());
+    if array.is_null() {
+        return Err("failed to allocate the arguments of main".to_owned().into());
+    }
+    for (index, string) in (0..len).zip(strings) {
+        let element = new_string(env, string)?;
+        ((**env).v1_1.SetObjectArrayElement)(env, array, index, element);
+        ((**env).v1_1.DeleteLocalRef)(env, element);
+    }
+    Ok(array)
+}
+
+/// Returns a new `java.lang.String` with the contents of `string`
+///
+/// The string is passed as UTF-16 rather than the modified UTF-8 of `NewStringUTF`, so any
+/// Rust string converts exactly.
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn new_string(env: *mut JNIEnv, string: &str) -> Result<jstring, Failure> {
+    let chars: Vec<jchar> = string.encode_utf16().collect();
+    let len = jsize::try_from(chars.len()).map_err(|_| "argument too long".to_owned())?;
+    let string = ((**env).v1_1.NewString)(env, chars.as_ptr(), len);
+    if string.is_null() {
+        return Err("failed to allocate a string".to_owned().into());
+    }
+    Ok(string)
+}
+
+/// Returns the contents of a `java.lang.String`
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread, and `string` a non-null string.
+unsafe fn rust_string(env: *mut JNIEnv, string: jstring) -> String {
+    let len = ((**env).v1_1.GetStringLength)(env, string);
+    let mut chars = vec![0; len.max(0) as usize];
+    ((**env).v1_2.GetStringRegion)(env, string, 0, len, chars.as_mut_ptr());
+    String::from_utf16_lossy(&chars)
+}
+
+/// Finds a class of the JDK
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread, and `name` NUL terminated.
+unsafe fn find_class(env: *mut JNIEnv, name: &[u8]) -> Result<jclass, Failure> {
+    let name = cstr(name);
+    let class = ((**env).v1_1.FindClass)(env, name.as_ptr());
+    if class.is_null() {
+        return Err(format!("failed to find {}", name.to_string_lossy()).into());
+    }
+    Ok(class)
+}
+
+/// Finds an instance method of a JDK class
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread, `class` a valid class, and `name` and
+/// `descriptor` NUL terminated.
+unsafe fn method(
+    env: *mut JNIEnv,
+    class: jclass,
+    name: &[u8],
+    descriptor: &[u8],
+) -> Result<jmethodID, Failure> {
+    let name = cstr(name);
+    let method = ((**env).v1_1.GetMethodID)(env, class, name.as_ptr(), cstr(descriptor).as_ptr());
+    if method.is_null() {
+        return Err(format!("failed to find method {}", name.to_string_lossy()).into());
+    }
+    Ok(method)
+}
+
+/// Returns whether an exception is pending
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn exception_check(env: *mut JNIEnv) -> bool {
+    ((**env).v1_2.ExceptionCheck)(env) == JNI_TRUE
+}
+
+/// Clears the pending exception, returning whether there was one
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn exception_clear(env: *mut JNIEnv) -> bool {
+    let pending = exception_check(env);
+    if pending {
+        ((**env).v1_1.ExceptionClear)(env);
+    }
+    pending
+}
+
+/// Prints and clears the pending exception, if there is one
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+unsafe fn describe_exception(env: *mut JNIEnv) {
+    if exception_check(env) {
+        ((**env).v1_1.ExceptionDescribe)(env);
+    }
+}
+
+fn cstr(bytes: &[u8]) -> &CStr {
+    CStr::from_bytes_with_nul(bytes).expect("names are NUL terminated")
+}
@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const HELLO: &str = r#"
+public class Hello {
+    public static void main(String[] args) throws Exception {
+        System.out.println(String.join(",", args));
+        switch (args.length > 0 ? args[0] : "") {
+            case "exit":
+                System.exit(Integer.parseInt(args[1]));
+            case "throw":
+                throw new IllegalStateException("thrown from main");
+            case "thread":
+                Thread thread = new Thread(() -> {
+                    try {
+                        Thread.sleep(200);
+                    } catch (InterruptedException e) {
+                        throw new RuntimeException(e);
+                    }
+                    System.out.println("thread done");
+                });
+                thread.start();
+                break;
+            case "unicode":
+                System.out.println(args[1].equals("caf\u00e9 \ud83d\ude00"));
+                break;
+        }
+    }
+}
+"#;
+
+fn launcher(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_java-launcher"))
+        .args(args)
+        .env_remove("JDK_JAVA_OPTIONS")
+        .env_remove("CLASSPATH")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Compiles `Hello.java` with `javac` run by the launcher, and packages it in `hello.jar`
+fn build_hello() -> (PathBuf, PathBuf) {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("launch");
+    let classes = dir.join("classes");
+    fs::create_dir_all(&classes).unwrap();
+    let source = dir.join("Hello.java");
+    fs::write(&source, HELLO).unwrap();
+
+    let javac = launcher(&[
+        "com.sun.tools.javac.Main",
+        "-d",
+        classes.to_str().unwrap(),
+        source.to_str().unwrap(),
+    ]);
+    assert!(javac.status.success(), "{}", stderr(&javac));
+
+    let jar = dir.join("hello.jar");
+    let _ = fs::remove_file(&jar);
+    let jar_tool = launcher(&[
+        "sun.tools.jar.Main",
+        "--create",
+        "--file",
+        jar.to_str().unwrap(),
+        "--main-class",
+        "Hello",
+        "-C",
+        classes.to_str().unwrap(),
+        "Hello.class",
+    ]);
+    assert!(jar_tool.status.success(), "{}", stderr(&jar_tool));
+    (classes, jar)
+}
+
+#[test]
+fn usage() {
+    let output = launcher(&[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("Usage: java-launcher"));
+
+    let output = launcher(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("Usage: java-launcher"));
+}
+
+#[test]
+fn errors() {
+    let output = launcher(&["-version"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("`-version` is only supported by the java launcher"));
+
+    let output = launcher(&["-cp", "does-not-exist", "Missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    let message = stderr(&output);
+    assert!(message.contains("Error: Could not find or load main class Missing"));
+    assert!(message.contains("java.lang.NoClassDefFoundError: Missing"));
+
+    let output = launcher(&["java.lang.Object"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("Main method not found in class java.lang.Object"));
+
+    let corrupt = Path::new(env!("CARGO_TARGET_TMPDIR")).join("corrupt.jar");
+    fs::write(&corrupt, "not a zip file").unwrap();
+    let output = launcher(&["-jar", corrupt.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let message = stderr(&output);
+    assert!(
+        message.contains("Error: Invalid or corrupt jarfile"),
+        "{message}"
+    );
+    assert!(!message.contains("Exception"), "{message}");
+
+    let output = launcher(&["-XX:+NoSuchVmOption", "Hello"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("Could not create the Java Virtual Machine"));
+}
+
+#[test]
+fn launch() {
+    let (classes, jar) = build_hello();
+    let classes = classes.to_str().unwrap();
+    let jar = jar.to_str().unwrap();
+
+    for launch in [&["-cp", classes, "Hello"][..], &["-jar", jar]] {
+        let run = |args: &[&str]| launcher(&[launch, args].concat());
+
+        let output = run(&["a", "b c"]);
+        assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+        assert_eq!(stdout(&output), "a,b c\n");
+
+        let output = run(&["exit", "42"]);
+        assert_eq!(output.status.code(), Some(42));
+
+        let output = run(&["throw"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output)
+            .contains("Exception in thread \"main\" java.lang.IllegalStateException"));
+
+        // `DestroyJavaVM` waits for the non-daemon thread
+        let output = run(&["thread"]);
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(stdout(&output), "thread\nthread done\n");
+
+        let output = run(&["unicode", "caf\u{e9} \u{1f600}"]);
+        assert_eq!(stdout(&output).lines().last(), Some("true"));
+    }
+}
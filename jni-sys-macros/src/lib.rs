@@ -3,8 +3,11 @@ extern crate proc_macro;
 use std::{cmp::Ordering, collections::HashSet};
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
-use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, Ident, LitStr};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput, Expr, Fields,
+    FnArg, Ident, ItemFn, LitBool, LitStr, Token, Type,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct JniVersion {
@@ -58,7 +61,37 @@ impl syn::parse::Parse for JniVersion {
     }
 }
 
-fn jni_to_union_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+/// The arguments of `#[jni_to_union]`
+struct JniToUnionArgs {
+    /// The prefix of the version constants named in the docs
+    version_prefix: String,
+    /// The version of fields without `#[jni_added]`
+    default_version: JniVersion,
+}
+
+impl Default for JniToUnionArgs {
+    fn default() -> Self {
+        Self {
+            version_prefix: "JNI_VERSION".to_string(),
+            default_version: JniVersion::default(),
+        }
+    }
+}
+
+impl JniToUnionArgs {
+    fn parse_meta(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("version_prefix") {
+            self.version_prefix = meta.value()?.parse::<LitStr>()?.value();
+        } else if meta.path.is_ident("default_version") {
+            self.default_version = meta.value()?.parse()?;
+        } else {
+            return Err(meta.error("expected `version_prefix` or `default_version`"));
+        }
+        Ok(())
+    }
+}
+
+fn jni_to_union_impl(args: JniToUnionArgs, input: DeriveInput) -> syn::Result<TokenStream> {
     let original_name = &input.ident;
     let original_visibility = &input.vis;
 
@@ -68,8 +101,8 @@ fn jni_to_union_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     if let Data::Struct(data) = &input.data {
         if let Fields::Named(fields) = &data.fields {
             for field in &fields.named {
-                // Default to version 1.1
-                let mut min_version = JniVersion::default();
+                // Default to version 1.1, unless the union is for another interface
+                let mut min_version = args.default_version;
 
                 let mut field = field.clone();
 
@@ -94,6 +127,11 @@ fn jni_to_union_impl(input: DeriveInput) -> syn::Result<TokenStream> {
             // Quote structs and union
             let mut expanded = quote! {};
 
+            let interface = args
+                .version_prefix
+                .strip_suffix("_VERSION")
+                .unwrap_or(&args.version_prefix);
+
             let mut union_members = quote!();
 
             let mut versions: Vec<_> = versions.into_iter().collect();
@@ -159,8 +197,10 @@ fn jni_to_union_impl(input: DeriveInput) -> syn::Result<TokenStream> {
                     }
                 });
 
-                let api_comment =
-                    format!("API when JNI version >= `JNI_VERSION_{}`", version_suffix);
+                let api_comment = format!(
+                    "API when {} version >= `{}_{}`",
+                    interface, args.version_prefix, version_suffix
+                );
                 union_members.extend(quote! {
                     #[doc = #api_comment]
                     #original_visibility #version_ident: #struct_ident,
@@ -184,12 +224,391 @@ fn jni_to_union_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     ))
 }
 
+/// Turns a function table struct into a union of one struct per interface version
+///
+/// Fields are added in JNI 1.1 unless marked `#[jni_added("<version>")]`. Tables of other
+/// interfaces, such as JVMTI, set the prefix of their version constants and their oldest
+/// version with `#[jni_to_union(version_prefix = "JVMTI_VERSION", default_version = "1.0")]`.
 #[proc_macro_attribute]
-pub fn jni_to_union(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn jni_to_union(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = JniToUnionArgs::default();
+    let parser = syn::meta::parser(|meta| args.parse_meta(meta));
+    parse_macro_input!(attr with parser);
     let input = parse_macro_input!(item as DeriveInput);
 
-    match jni_to_union_impl(input) {
+    match jni_to_union_impl(args, input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+struct JArgs {
+    descriptor: Option<LitStr>,
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl syn::parse::Parse for JArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let descriptor = if input.peek(LitStr) && input.peek2(Token![;]) {
+            let descriptor = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Some(descriptor)
+        } else {
+            None
+        };
+        let args = Punctuated::parse_terminated(input)?;
+        Ok(JArgs { descriptor, args })
+    }
+}
+
+/// Maps each parameter of a method descriptor to the `jvalue` member used to pass it
+fn descriptor_param_members(descriptor: &str) -> Result<Vec<&'static str>, String> {
+    let bytes = descriptor.as_bytes();
+    if bytes.first() != Some(&b'(') {
+        return Err("method descriptor must start with '('".to_string());
+    }
+    let mut members = vec![];
+    let mut pos = 1;
+    loop {
+        match bytes.get(pos) {
+            None => return Err("method descriptor is missing ')'".to_string()),
+            Some(b')') => break,
+            Some(_) => {
+                let (member, next) = descriptor_field_member(bytes, pos)?;
+                members.push(member);
+                pos = next;
+            }
+        }
+    }
+    pos += 1;
+    match bytes.get(pos) {
+        Some(b'V') => pos += 1,
+        Some(_) => pos = descriptor_field_member(bytes, pos)?.1,
+        None => return Err("method descriptor is missing a return type".to_string()),
+    }
+    if pos != bytes.len() {
+        return Err(format!("unexpected trailing characters at offset {pos}"));
+    }
+    Ok(members)
+}
+
+fn descriptor_field_member(bytes: &[u8], mut pos: usize) -> Result<(&'static str, usize), String> {
+    let start = pos;
+    while bytes.get(pos) == Some(&b'[') {
+        pos += 1;
+    }
+    let member = match bytes.get(pos) {
+        Some(b'Z') => "z",
+        Some(b'B') => "b",
+        Some(b'C') => "c",
+        Some(b'S') => "s",
+        Some(b'I') => "i",
+        Some(b'J') => "j",
+        Some(b'F') => "f",
+        Some(b'D') => "d",
+        Some(b'L') => {
+            let len = bytes[pos..]
+                .iter()
+                .position(|b| *b == b';')
+                .ok_or_else(|| format!("unterminated class name at offset {pos}"))?;
+            if len == 1 {
+                return Err(format!("empty class name at offset {pos}"));
+            }
+            pos += len;
+            "l"
+        }
+        Some(other) => {
+            return Err(format!(
+                "unexpected '{}' at offset {pos}",
+                char::from(*other).escape_default()
+            ))
+        }
+        None => return Err(format!("missing type at offset {pos}")),
+    };
+    let member = if pos > start { "l" } else { member };
+    Ok((member, pos + 1))
+}
+
+fn jargs_impl(input: JArgs) -> syn::Result<TokenStream> {
+    let Some(descriptor) = input.descriptor else {
+        let len = input.args.len();
+        let values = input.args.iter().map(|arg| {
+            quote_spanned! {arg.span()=> ::jni_sys::jvalue::from(#arg) }
+        });
+        return Ok(quote! {{
+            let args: [::jni_sys::jvalue; #len] = [#(#values),*];
+            args
+        }}
+        .into());
+    };
+
+    let members = descriptor_param_members(&descriptor.value()).map_err(|err| {
+        syn::Error::new(
+            descriptor.span(),
+            format!("invalid method descriptor: {err}"),
+        )
+    })?;
+    if members.len() != input.args.len() {
+        return Err(syn::Error::new(
+            descriptor.span(),
+            format!(
+                "method descriptor takes {} argument(s) but {} were supplied",
+                members.len(),
+                input.args.len()
+            ),
+        ));
+    }
+    let len = members.len();
+    let values = members.iter().zip(&input.args).map(|(member, arg)| {
+        let member = Ident::new(member, arg.span());
+        quote_spanned! {arg.span()=> ::jni_sys::jvalue { #member: #arg } }
+    });
+    Ok(quote! {{
+        let args: [::jni_sys::jvalue; #len] = [#(#values),*];
+        args
+    }}
+    .into())
+}
+
+/// Builds a `[jvalue; N]` argument array for the `Call*MethodA` family of functions
+///
+/// Each argument is converted with `jvalue::from`. If the argument list is prefixed with a
+/// method descriptor literal and a `;` then the number of arguments and the type of each
+/// argument are checked against the descriptor at compile time.
+#[proc_macro]
+pub fn jargs(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as JArgs);
+
+    match jargs_impl(input) {
         Ok(tokens) => tokens,
         Err(err) => err.into_compile_error().into(),
     }
 }
+
+#[derive(Default)]
+struct JniExportArgs {
+    class: Option<LitStr>,
+    method: Option<LitStr>,
+    descriptor: Option<LitStr>,
+    overloaded: bool,
+}
+
+impl JniExportArgs {
+    fn parse_meta(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("class") {
+            self.class = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("method") {
+            self.method = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("descriptor") {
+            self.descriptor = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("overloaded") {
+            self.overloaded = meta.value()?.parse::<LitBool>()?.value;
+        } else {
+            return Err(meta.error("expected `class`, `method`, `descriptor` or `overloaded`"));
+        }
+        Ok(())
+    }
+}
+
+/// Appends `s` mangled as part of a native method symbol name
+fn push_mangled(symbol: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => symbol.push(c),
+            '/' | '.' => symbol.push('_'),
+            '_' => symbol.push_str("_1"),
+            ';' => symbol.push_str("_2"),
+            '[' => symbol.push_str("_3"),
+            _ => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    symbol.push_str(&format!("_0{unit:04x}"));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the last path segment of a type, if it is a plain path
+fn type_name(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().map(|segment| &segment.ident)
+        }
+        Type::Group(group) => type_name(&group.elem),
+        Type::Paren(paren) => type_name(&paren.elem),
+        _ => None,
+    }
+}
+
+/// Returns the field descriptor for a JNI parameter type
+fn type_descriptor(ty: &Type) -> Option<&'static str> {
+    Some(match type_name(ty)?.to_string().as_str() {
+        "jboolean" | "bool" => "Z",
+        "jbyte" | "i8" => "B",
+        "jchar" | "u16" => "C",
+        "jshort" | "i16" => "S",
+        "jint" | "jsize" | "i32" => "I",
+        "jlong" | "i64" => "J",
+        "jfloat" | "f32" => "F",
+        "jdouble" | "f64" => "D",
+        "jobject" | "jweak" => "Ljava/lang/Object;",
+        "jclass" => "Ljava/lang/Class;",
+        "jstring" => "Ljava/lang/String;",
+        "jthrowable" => "Ljava/lang/Throwable;",
+        "jbooleanArray" => "[Z",
+        "jbyteArray" => "[B",
+        "jcharArray" => "[C",
+        "jshortArray" => "[S",
+        "jintArray" => "[I",
+        "jlongArray" => "[J",
+        "jfloatArray" => "[F",
+        "jdoubleArray" => "[D",
+        "jobjectArray" => "[Ljava/lang/Object;",
+        _ => return None,
+    })
+}
+
+fn jni_export_impl(args: JniExportArgs, item: &ItemFn) -> syn::Result<TokenStream> {
+    let sig = &item.sig;
+    let is_system_abi = sig
+        .abi
+        .as_ref()
+        .and_then(|abi| abi.name.as_ref())
+        .is_some_and(|name| name.value() == "system");
+    if !is_system_abi {
+        return Err(syn::Error::new(
+            sig.fn_token.span(),
+            "JNI native methods must be declared `extern \"system\"`",
+        ));
+    }
+    let Some(class) = args.class else {
+        return Err(syn::Error::new(
+            sig.ident.span(),
+            "missing `class = \"...\"` argument",
+        ));
+    };
+
+    let mut inputs = sig.inputs.iter().map(|input| match input {
+        FnArg::Typed(typed) => Ok(&*typed.ty),
+        FnArg::Receiver(receiver) => Err(syn::Error::new(
+            receiver.span(),
+            "JNI native methods can't take `self`",
+        )),
+    });
+    match inputs.next().transpose()? {
+        Some(Type::Ptr(ptr))
+            if ptr.mutability.is_some()
+                && type_name(&ptr.elem).is_some_and(|name| name == "JNIEnv") => {}
+        Some(ty) => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "the first parameter of a JNI native method must be `*mut JNIEnv`",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new(
+                sig.paren_token.span.join(),
+                "JNI native methods must take `*mut JNIEnv` and `jobject` or `jclass` parameters",
+            ))
+        }
+    }
+    match inputs.next().transpose()? {
+        Some(ty) if type_name(ty).is_some_and(|name| name == "jobject" || name == "jclass") => {}
+        Some(ty) => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "the second parameter of a JNI native method must be `jobject` or `jclass`",
+            ))
+        }
+        None => return Err(syn::Error::new(
+            sig.paren_token.span.join(),
+            "JNI native methods must take a `jobject` or `jclass` parameter after `*mut JNIEnv`",
+        )),
+    }
+    let params = inputs.collect::<syn::Result<Vec<_>>>()?;
+
+    let method = args
+        .method
+        .map(|method| method.value())
+        .unwrap_or_else(|| sig.ident.to_string());
+    let mut symbol = "Java_".to_string();
+    push_mangled(&mut symbol, &class.value());
+    symbol.push('_');
+    push_mangled(&mut symbol, &method);
+
+    if let Some(descriptor) = &args.descriptor {
+        let members = descriptor_param_members(&descriptor.value()).map_err(|err| {
+            syn::Error::new(
+                descriptor.span(),
+                format!("invalid method descriptor: {err}"),
+            )
+        })?;
+        if members.len() != params.len() {
+            return Err(syn::Error::new(
+                descriptor.span(),
+                format!(
+                    "method descriptor takes {} argument(s) but the function takes {}",
+                    members.len(),
+                    params.len()
+                ),
+            ));
+        }
+    }
+    if args.overloaded {
+        let params_descriptor = match &args.descriptor {
+            Some(descriptor) => {
+                let descriptor = descriptor.value();
+                descriptor[1..descriptor.find(')').unwrap_or(1)].to_string()
+            }
+            None => params
+                .iter()
+                .map(|ty| {
+                    type_descriptor(ty).ok_or_else(|| {
+                        syn::Error::new(
+                            ty.span(),
+                            "can't derive a descriptor for this type, use `descriptor = \"...\"`",
+                        )
+                    })
+                })
+                .collect::<syn::Result<String>>()?,
+        };
+        symbol.push_str("__");
+        push_mangled(&mut symbol, &params_descriptor);
+    }
+
+    Ok(quote! {
+        #[export_name = #symbol]
+        #item
+    }
+    .into())
+}
+
+/// Exports an `extern "system"` function as a JNI native method
+///
+/// The function is exported under the mangled `Java_<class>_<method>` symbol name that the JVM
+/// looks up for a `native` method that wasn't registered with `RegisterNatives`. The first two
+/// parameters must be `*mut JNIEnv` and `jobject` (or `jclass` for static methods).
+///
+/// Arguments:
+/// - `class = "com.example.Foo"`: the class declaring the method (required)
+/// - `method = "name"`: the Java method name, if it differs from the function name
+/// - `overloaded = true`: use the long symbol name, which is needed for overloaded methods. The
+///   parameter descriptors are derived from the JNI parameter types.
+/// - `descriptor = "(Lcom/example/Bar;)V"`: the method descriptor to use for the long symbol
+///   name instead, for parameters whose class can't be derived from their type
+#[proc_macro_attribute]
+pub fn jni_export(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = JniExportArgs::default();
+    let parser = syn::meta::parser(|meta| args.parse_meta(meta));
+    parse_macro_input!(attr with parser);
+    let item = parse_macro_input!(item as ItemFn);
+
+    match jni_export_impl(args, &item) {
+        Ok(tokens) => tokens,
+        // Keep the function so that the error isn't followed by unrelated ones about it missing
+        Err(err) => {
+            let err = err.into_compile_error();
+            quote! { #err #item }.into()
+        }
+    }
+}
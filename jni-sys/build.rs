@@ -0,0 +1,97 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+#[allow(dead_code)]
+#[path = "src/jdk.rs"]
+mod jdk;
+
+use jdk::{Jdk, JdkSource};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/jdk.rs");
+    if env::var_os("CARGO_FEATURE_LINK_JVM").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-env-changed=JAVA_HOME");
+    println!("cargo:rerun-if-env-changed=JNI_SYS_LIBJVM_DIR");
+    println!("cargo:rerun-if-env-changed=JNI_SYS_LIBJVM_RPATH");
+
+    if env::var_os("CARGO_FEATURE_DYNAMIC").is_some() {
+        println!(
+            "cargo:warning=the `link-jvm` feature has no effect on the invocation API functions \
+             when the `dynamic` feature is enabled"
+        );
+    }
+
+    let windows = env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "windows");
+    let (link_dir, runtime_dir) = match libjvm_dirs(windows) {
+        Ok(dirs) => dirs,
+        Err(message) => {
+            eprintln!("error: {message}");
+            process::exit(1);
+        }
+    };
+
+    println!("cargo:rustc-link-search=native={}", link_dir.display());
+    println!("cargo:rustc-link-lib=dylib=jvm");
+    // Cargo only applies link arguments to this package's own targets, so this is for running
+    // the tests and examples of jni-sys. Dependents have to add their own rpath.
+    let rpath = env::var_os("JNI_SYS_LIBJVM_RPATH").is_some_and(|rpath| rpath != "0");
+    if rpath && !windows {
+        println!("cargo:rustc-link-arg=-Wl,-rpath,{}", runtime_dir.display());
+    }
+}
+
+/// Returns the directory to link `jvm` from and the directory the JVM library is loaded from
+///
+/// These differ on Windows, where the `jvm.lib` import library is in `lib` and `jvm.dll` is
+/// in `bin/server`.
+fn libjvm_dirs(windows: bool) -> Result<(PathBuf, PathBuf), String> {
+    if let Some(dir) = env::var_os("JNI_SYS_LIBJVM_DIR").filter(|dir| !dir.is_empty()) {
+        let dir = PathBuf::from(dir);
+        if !dir.is_dir() {
+            return Err(format!(
+                "JNI_SYS_LIBJVM_DIR is set to {}, which is not a directory. Set it to the \
+                 directory containing the JVM library, or unset it to use JAVA_HOME.",
+                dir.display()
+            ));
+        }
+        return Ok((dir.clone(), dir));
+    }
+
+    let Some(java_home) = env::var_os("JAVA_HOME").filter(|home| !home.is_empty()) else {
+        let message = "the `link-jvm` feature of jni-sys needs to find the JVM library to link. \
+                       Set JAVA_HOME to a JDK install, or JNI_SYS_LIBJVM_DIR to the directory \
+                       containing the JVM library.";
+        return Err(message.to_owned());
+    };
+    let java_home = PathBuf::from(java_home);
+    let Some(jdk) = Jdk::at(&java_home, JdkSource::JavaHome) else {
+        return Err(format!(
+            "JAVA_HOME is set to {}, which doesn't look like a JDK (it has no `release` file, \
+             `bin/java` or JVM library). Point JAVA_HOME at a JDK install, or set \
+             JNI_SYS_LIBJVM_DIR to the directory containing the JVM library.",
+            java_home.display()
+        ));
+    };
+    let Some(libjvm) = jdk.default_libjvm() else {
+        return Err(format!(
+            "no JVM library was found in the JDK at {}. Install a full JDK, or set \
+             JNI_SYS_LIBJVM_DIR to the directory containing the JVM library.",
+            jdk.home.display()
+        ));
+    };
+    let runtime_dir = libjvm
+        .path
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| jdk.home.clone());
+    let link_dir = if windows {
+        jdk.home.join("lib")
+    } else {
+        runtime_dir.clone()
+    };
+    Ok((link_dir, runtime_dir))
+}
@@ -0,0 +1,618 @@
+//! Parsing of JNI field and method descriptors
+//!
+//! Descriptors are the type strings passed to `GetFieldID`, `GetMethodID` and friends, and used
+//! for `JNINativeMethod::signature`, such as `"Ljava/lang/String;"` or `"(I[J)Z"`.
+//!
+//! Parsing never allocates: the parsed types borrow class names from the input string.
+//!
+//! Descriptors can also be built at compile time from Rust types with [`JavaType`] and
+//! [`method_descriptor!`](crate::method_descriptor).
+
+use core::fmt;
+
+use crate::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jobject, jshort};
+
+/// One of the eight Java primitive types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrimitiveType {
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+}
+
+impl PrimitiveType {
+    /// Returns the primitive type for a descriptor character such as `b'I'`
+    pub const fn from_descriptor_char(c: u8) -> Option<Self> {
+        Some(match c {
+            b'Z' => Self::Boolean,
+            b'B' => Self::Byte,
+            b'C' => Self::Char,
+            b'S' => Self::Short,
+            b'I' => Self::Int,
+            b'J' => Self::Long,
+            b'F' => Self::Float,
+            b'D' => Self::Double,
+            _ => return None,
+        })
+    }
+
+    /// Returns the descriptor character for this type, such as `'I'` for `int`
+    pub const fn descriptor_char(self) -> char {
+        match self {
+            Self::Boolean => 'Z',
+            Self::Byte => 'B',
+            Self::Char => 'C',
+            Self::Short => 'S',
+            Self::Int => 'I',
+            Self::Long => 'J',
+            Self::Float => 'F',
+            Self::Double => 'D',
+        }
+    }
+
+    /// Returns the number of argument slots taken by a value of this type
+    ///
+    /// `long` and `double` take two slots, everything else takes one.
+    pub const fn slots(self) -> usize {
+        match self {
+            Self::Long | Self::Double => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns the kind of value of this type
+    pub const fn kind(self) -> TypeKind {
+        match self {
+            Self::Boolean => TypeKind::Boolean,
+            Self::Byte => TypeKind::Byte,
+            Self::Char => TypeKind::Char,
+            Self::Short => TypeKind::Short,
+            Self::Int => TypeKind::Int,
+            Self::Long => TypeKind::Long,
+            Self::Float => TypeKind::Float,
+            Self::Double => TypeKind::Double,
+        }
+    }
+}
+
+/// The kind of value a descriptor denotes
+///
+/// This maps one to one onto the `Call<Type>Method`, `Get<Type>Field` and `jvalue` variants,
+/// with all class and array types collapsed into [`TypeKind::Object`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    Void,
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Object,
+}
+
+/// The element type of an array descriptor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ElementType<'a> {
+    Primitive(PrimitiveType),
+    /// A class, given by its internal name such as `java/lang/String`
+    Class(&'a str),
+}
+
+impl fmt::Display for ElementType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Primitive(primitive) => write!(f, "{}", primitive.descriptor_char()),
+            Self::Class(name) => write!(f, "L{name};"),
+        }
+    }
+}
+
+/// A parsed field descriptor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldType<'a> {
+    Primitive(PrimitiveType),
+    /// A class, given by its internal name such as `java/lang/String`
+    Class(&'a str),
+    /// An array with the given number of dimensions (at least one) and element type
+    Array {
+        dimensions: u8,
+        element: ElementType<'a>,
+    },
+}
+
+impl<'a> FieldType<'a> {
+    /// Parses a complete field descriptor such as `"[Ljava/lang/String;"`
+    pub fn parse(descriptor: &'a str) -> Result<Self, DescriptorError> {
+        let (field, end) = parse_field(descriptor, 0)?;
+        if end != descriptor.len() {
+            return Err(DescriptorError::new(end, DescriptorErrorKind::TrailingData));
+        }
+        Ok(field)
+    }
+
+    /// Returns the kind of value of this type
+    pub const fn kind(&self) -> TypeKind {
+        match self {
+            Self::Primitive(primitive) => primitive.kind(),
+            Self::Class(_) | Self::Array { .. } => TypeKind::Object,
+        }
+    }
+
+    /// Returns the number of argument slots taken by a value of this type
+    pub const fn slots(&self) -> usize {
+        match self {
+            Self::Primitive(primitive) => primitive.slots(),
+            Self::Class(_) | Self::Array { .. } => 1,
+        }
+    }
+}
+
+impl fmt::Display for FieldType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Primitive(primitive) => write!(f, "{}", primitive.descriptor_char()),
+            Self::Class(name) => write!(f, "L{name};"),
+            Self::Array {
+                dimensions,
+                element,
+            } => {
+                for _ in 0..*dimensions {
+                    f.write_str("[")?;
+                }
+                write!(f, "{element}")
+            }
+        }
+    }
+}
+
+/// The return type of a method descriptor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReturnType<'a> {
+    Void,
+    Field(FieldType<'a>),
+}
+
+impl ReturnType<'_> {
+    /// Returns the kind of value returned
+    pub const fn kind(&self) -> TypeKind {
+        match self {
+            Self::Void => TypeKind::Void,
+            Self::Field(field) => field.kind(),
+        }
+    }
+}
+
+impl fmt::Display for ReturnType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Void => f.write_str("V"),
+            Self::Field(field) => write!(f, "{field}"),
+        }
+    }
+}
+
+/// A parsed method descriptor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MethodDescriptor<'a> {
+    params: &'a str,
+    param_count: usize,
+    param_slots: usize,
+    ret: ReturnType<'a>,
+}
+
+impl<'a> MethodDescriptor<'a> {
+    /// Parses a complete method descriptor such as `"(ILjava/lang/String;)V"`
+    pub fn parse(descriptor: &'a str) -> Result<Self, DescriptorError> {
+        if !descriptor.starts_with('(') {
+            return Err(DescriptorError::new(0, unexpected(descriptor, 0)));
+        }
+        let mut pos = 1;
+        let mut param_count = 0;
+        let mut param_slots = 0;
+        loop {
+            match descriptor.as_bytes().get(pos) {
+                Some(b')') => break,
+                _ => {
+                    let (param, end) = parse_field(descriptor, pos)?;
+                    param_count += 1;
+                    param_slots += param.slots();
+                    pos = end;
+                }
+            }
+        }
+        let params = &descriptor[1..pos];
+        pos += 1;
+        let (ret, end) = if descriptor.as_bytes().get(pos) == Some(&b'V') {
+            (ReturnType::Void, pos + 1)
+        } else {
+            let (field, end) = parse_field(descriptor, pos)?;
+            (ReturnType::Field(field), end)
+        };
+        if end != descriptor.len() {
+            return Err(DescriptorError::new(end, DescriptorErrorKind::TrailingData));
+        }
+        Ok(Self {
+            params,
+            param_count,
+            param_slots,
+            ret,
+        })
+    }
+
+    /// Returns an iterator over the parameter types
+    pub fn params(&self) -> Params<'a> {
+        Params {
+            remaining: self.params,
+        }
+    }
+
+    /// Returns the parameter descriptors between the parentheses, such as `"ILjava/lang/String;"`
+    pub const fn params_descriptor(&self) -> &'a str {
+        self.params
+    }
+
+    /// Returns the number of parameters
+    pub const fn param_count(&self) -> usize {
+        self.param_count
+    }
+
+    /// Returns the number of argument slots taken by the parameters
+    ///
+    /// This counts `long` and `double` parameters twice and does not include the slot taken by
+    /// `this` for instance methods.
+    pub const fn param_slots(&self) -> usize {
+        self.param_slots
+    }
+
+    /// Returns the return type
+    pub const fn return_type(&self) -> ReturnType<'a> {
+        self.ret
+    }
+
+    /// Returns the kind of value returned, which selects the `Call<Type>Method` function to use
+    pub const fn return_kind(&self) -> TypeKind {
+        self.ret.kind()
+    }
+}
+
+impl fmt::Display for MethodDescriptor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}){}", self.params, self.ret)
+    }
+}
+
+/// Iterator over the parameter types of a [`MethodDescriptor`]
+#[derive(Clone, Debug)]
+pub struct Params<'a> {
+    remaining: &'a str,
+}
+
+impl<'a> Iterator for Params<'a> {
+    type Item = FieldType<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        // The parameters were validated when the descriptor was parsed
+        let (param, end) = parse_field(self.remaining, 0).ok()?;
+        self.remaining = &self.remaining[end..];
+        Some(param)
+    }
+}
+
+/// Why a descriptor failed to parse
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DescriptorErrorKind {
+    /// The descriptor ended where a type was expected
+    UnexpectedEnd,
+    /// A character that can't start a type (or a method descriptor) was found
+    UnexpectedChar(char),
+    /// `V` was used as a field or parameter type
+    VoidParameter,
+    /// A class name was empty, had an empty package segment or contained `.` or `[`
+    InvalidClassName,
+    /// A class name was not terminated by `;`
+    UnterminatedClassName,
+    /// An array type had more than 255 dimensions
+    TooManyDimensions,
+    /// Characters were left over after a complete descriptor
+    TrailingData,
+}
+
+/// Error returned when a descriptor fails to parse
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DescriptorError {
+    offset: usize,
+    kind: DescriptorErrorKind,
+}
+
+impl DescriptorError {
+    const fn new(offset: usize, kind: DescriptorErrorKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// Returns the byte offset into the descriptor at which the error was found
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the reason the descriptor is invalid
+    pub const fn kind(&self) -> DescriptorErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DescriptorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DescriptorErrorKind::UnexpectedEnd => f.write_str("unexpected end of descriptor")?,
+            DescriptorErrorKind::UnexpectedChar(c) => {
+                write!(f, "unexpected character '{}'", c.escape_default())?
+            }
+            DescriptorErrorKind::VoidParameter => {
+                f.write_str("void is only valid as a return type")?
+            }
+            DescriptorErrorKind::InvalidClassName => f.write_str("invalid class name")?,
+            DescriptorErrorKind::UnterminatedClassName => {
+                f.write_str("class name is not terminated by ';'")?
+            }
+            DescriptorErrorKind::TooManyDimensions => {
+                f.write_str("array has more than 255 dimensions")?
+            }
+            DescriptorErrorKind::TrailingData => f.write_str("unexpected data after descriptor")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+fn unexpected(descriptor: &str, pos: usize) -> DescriptorErrorKind {
+    match descriptor[pos..].chars().next() {
+        Some(c) => DescriptorErrorKind::UnexpectedChar(c),
+        None => DescriptorErrorKind::UnexpectedEnd,
+    }
+}
+
+/// Parses one field type starting at `start`, returning it with the offset just past its end
+fn parse_field(descriptor: &str, start: usize) -> Result<(FieldType<'_>, usize), DescriptorError> {
+    let bytes = descriptor.as_bytes();
+    let mut pos = start;
+    while bytes.get(pos) == Some(&b'[') {
+        pos += 1;
+    }
+    let dimensions = pos - start;
+    if dimensions > u8::MAX as usize {
+        return Err(DescriptorError::new(
+            start,
+            DescriptorErrorKind::TooManyDimensions,
+        ));
+    }
+    let (element, end) = match bytes.get(pos) {
+        Some(b'L') => {
+            let (name, end) = parse_class_name(descriptor, pos + 1)?;
+            (ElementType::Class(name), end)
+        }
+        Some(b'V') => {
+            return Err(DescriptorError::new(
+                pos,
+                DescriptorErrorKind::VoidParameter,
+            ))
+        }
+        Some(c) => match PrimitiveType::from_descriptor_char(*c) {
+            Some(primitive) => (ElementType::Primitive(primitive), pos + 1),
+            None => return Err(DescriptorError::new(pos, unexpected(descriptor, pos))),
+        },
+        None => {
+            return Err(DescriptorError::new(
+                pos,
+                DescriptorErrorKind::UnexpectedEnd,
+            ))
+        }
+    };
+    let field = match (dimensions, element) {
+        (0, ElementType::Primitive(primitive)) => FieldType::Primitive(primitive),
+        (0, ElementType::Class(name)) => FieldType::Class(name),
+        (dimensions, element) => FieldType::Array {
+            dimensions: dimensions as u8,
+            element,
+        },
+    };
+    Ok((field, end))
+}
+
+/// Parses a class name starting just after its `L`, returning it with the offset past its `;`
+fn parse_class_name(descriptor: &str, start: usize) -> Result<(&str, usize), DescriptorError> {
+    let bytes = descriptor.as_bytes();
+    let mut pos = start;
+    let mut segment_start = start;
+    loop {
+        match bytes.get(pos) {
+            None => {
+                return Err(DescriptorError::new(
+                    start - 1,
+                    DescriptorErrorKind::UnterminatedClassName,
+                ))
+            }
+            Some(b';') | Some(b'/') if pos == segment_start => {
+                return Err(DescriptorError::new(
+                    pos,
+                    DescriptorErrorKind::InvalidClassName,
+                ))
+            }
+            Some(b';') => return Ok((&descriptor[start..pos], pos + 1)),
+            Some(b'/') => segment_start = pos + 1,
+            Some(b'.') | Some(b'[') => {
+                return Err(DescriptorError::new(
+                    pos,
+                    DescriptorErrorKind::InvalidClassName,
+                ))
+            }
+            Some(_) => {}
+        }
+        pos += 1;
+    }
+}
+
+/// A Rust type that corresponds to a Java type
+///
+/// The reference types (`jstring`, `jintArray`, etc) are all aliases of `jobject` and so share
+/// its descriptor, `Ljava/lang/Object;`. [`method_descriptor!`](crate::method_descriptor) tells
+/// them apart by name instead.
+pub trait JavaType {
+    /// The field descriptor for this type, such as `"I"` for `jint`
+    const DESCRIPTOR: &'static str;
+}
+
+macro_rules! impl_java_type {
+    ($($ty:ty => $descriptor:literal,)*) => {
+        $(
+        impl JavaType for $ty {
+            const DESCRIPTOR: &'static str = $descriptor;
+        }
+        )*
+    };
+}
+
+impl_java_type! {
+    jboolean => "Z",
+    jbyte => "B",
+    jchar => "C",
+    jshort => "S",
+    jint => "I",
+    jlong => "J",
+    jfloat => "F",
+    jdouble => "D",
+    jobject => "Ljava/lang/Object;",
+}
+
+/// Returns the length of the NUL terminated method descriptor built from `params` and `ret`
+#[doc(hidden)]
+pub const fn method_descriptor_len(params: &[&str], ret: &str) -> usize {
+    let mut len = 2 + ret.len() + 1;
+    let mut i = 0;
+    while i < params.len() {
+        len += params[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Concatenates `params` and `ret` into a NUL terminated method descriptor
+///
+/// `N` must be the length returned by [`method_descriptor_len`].
+#[doc(hidden)]
+pub const fn method_descriptor_bytes<const N: usize>(params: &[&str], ret: &str) -> [u8; N] {
+    const fn copy<const N: usize>(mut buf: [u8; N], mut pos: usize, s: &str) -> ([u8; N], usize) {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            buf[pos] = bytes[i];
+            pos += 1;
+            i += 1;
+        }
+        (buf, pos)
+    }
+
+    let (mut buf, mut pos) = copy([0; N], 0, "(");
+    let mut i = 0;
+    while i < params.len() {
+        (buf, pos) = copy(buf, pos, params[i]);
+        i += 1;
+    }
+    (buf, pos) = copy(buf, pos, ")");
+    (buf, pos) = copy(buf, pos, ret);
+    assert!(pos + 1 == N, "method descriptor length mismatch");
+    buf
+}
+
+/// Returns the descriptor for a JNI type name, distinguishing the `jobject` aliases by name
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __java_type_descriptor {
+    (jclass) => {
+        "Ljava/lang/Class;"
+    };
+    (jstring) => {
+        "Ljava/lang/String;"
+    };
+    (jthrowable) => {
+        "Ljava/lang/Throwable;"
+    };
+    (jbooleanArray) => {
+        "[Z"
+    };
+    (jbyteArray) => {
+        "[B"
+    };
+    (jcharArray) => {
+        "[C"
+    };
+    (jshortArray) => {
+        "[S"
+    };
+    (jintArray) => {
+        "[I"
+    };
+    (jlongArray) => {
+        "[J"
+    };
+    (jfloatArray) => {
+        "[F"
+    };
+    (jdoubleArray) => {
+        "[D"
+    };
+    (jobjectArray) => {
+        "[Ljava/lang/Object;"
+    };
+    ($ty:ident) => {
+        <$ty as $crate::descriptor::JavaType>::DESCRIPTOR
+    };
+}
+
+/// Builds the NUL terminated method descriptor for a native method's function type
+///
+/// The function type must take `*mut JNIEnv` and the receiver (`jobject` or `jclass`) followed
+/// by the Java parameters, all named by a single identifier. The Java types are taken from
+/// [`JavaType`], except that the `jobject` aliases such as `jstring` and `jintArray` map to
+/// their own descriptors. The result is a `&'static CStr` evaluated at compile time.
+///
+/// ```
+/// use jni_sys::*;
+///
+/// let descriptor = method_descriptor!(
+///     unsafe extern "system" fn(*mut JNIEnv, jclass, jstring, jintArray, jlong) -> jboolean
+/// );
+/// assert_eq!(descriptor.to_bytes(), b"(Ljava/lang/String;[IJ)Z");
+/// ```
+#[macro_export]
+macro_rules! method_descriptor {
+    ($(unsafe)? extern "system" fn($env:ty, $this:ty $(, $param:ident)* $(,)?) $(-> $ret:ident)?) => {{
+        const _: fn($env) -> *mut $crate::JNIEnv = |env| env;
+        const _: fn($this) -> $crate::jobject = |this| this;
+        const PARAMS: &[&str] = &[$($crate::__java_type_descriptor!($param)),*];
+        const RET: &str = $crate::method_descriptor!(@ret $($ret)?);
+        const LEN: usize = $crate::descriptor::method_descriptor_len(PARAMS, RET);
+        const BYTES: [u8; LEN] = $crate::descriptor::method_descriptor_bytes(PARAMS, RET);
+        const DESCRIPTOR: &::core::ffi::CStr =
+            match ::core::ffi::CStr::from_bytes_with_nul(&BYTES) {
+                Ok(descriptor) => descriptor,
+                Err(_) => panic!("method descriptor contains a NUL byte"),
+            };
+        DESCRIPTOR
+    }};
+    (@ret) => {
+        "V"
+    };
+    (@ret $ret:ident) => {
+        $crate::__java_type_descriptor!($ret)
+    };
+}
@@ -0,0 +1,966 @@
+//! The invocation API, for creating and finding Java VMs
+//!
+//! [`InvocationApi`] holds the three functions exported by the JVM library. By default they
+//! are the `extern` declarations at the root of this crate, which need `libjvm` at link time.
+//! With the `dynamic` feature those declarations are compiled out and the functions are instead
+//! looked up in a JVM library loaded at runtime with [`InvocationApi::load`].
+//!
+//! The `link-jvm` feature links `libjvm` from `JAVA_HOME` (or `JNI_SYS_LIBJVM_DIR`) at build
+//! time, but doesn't make binaries find it at runtime. A binary needs its own rpath, such as
+//! `cargo:rustc-link-arg=-Wl,-rpath,<dir>` from its build script, or the directory on the
+//! library search path (`LD_LIBRARY_PATH`, `DYLD_LIBRARY_PATH` or `PATH`). Setting
+//! `JNI_SYS_LIBJVM_RPATH=1` only adds an rpath to the tests and examples of jni-sys itself.
+//!
+//! The helpers on [`InvocationApi`] report failures as a [`JniError`], which maps the `JNI_E*`
+//! status codes returned by the invocation API and the `JavaVM` functions.
+
+use core::ffi::{c_void, CStr};
+use core::fmt;
+use core::ptr;
+use core::slice;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::registry;
+use crate::{
+    jint, jobject, jsize, JNIEnv, JavaVM, JavaVMAttachArgs, JavaVMInitArgs, JNI_EDETACHED,
+    JNI_EEXIST, JNI_EINVAL, JNI_ENOMEM, JNI_ERR, JNI_EVERSION, JNI_OK, JNI_VERSION_10,
+    JNI_VERSION_19, JNI_VERSION_1_1, JNI_VERSION_1_2, JNI_VERSION_1_4, JNI_VERSION_1_6,
+    JNI_VERSION_1_8, JNI_VERSION_20, JNI_VERSION_21, JNI_VERSION_24, JNI_VERSION_9,
+};
+
+/// The `JNI_VERSION_*` constants, newest first
+pub const JNI_VERSIONS: &[jint] = &[
+    JNI_VERSION_24,
+    JNI_VERSION_21,
+    JNI_VERSION_20,
+    JNI_VERSION_19,
+    JNI_VERSION_10,
+    JNI_VERSION_9,
+    JNI_VERSION_1_8,
+    JNI_VERSION_1_6,
+    JNI_VERSION_1_4,
+    JNI_VERSION_1_2,
+    JNI_VERSION_1_1,
+];
+
+/// Error for a status other than `JNI_OK` returned by a JNI function
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JniError {
+    /// `JNI_ERR`, an unknown error
+    Unknown,
+    /// `JNI_EDETACHED`, the thread isn't attached to the VM
+    Detached,
+    /// `JNI_EVERSION`, the JNI version isn't supported
+    Version,
+    /// `JNI_ENOMEM`, not enough memory
+    NoMemory,
+    /// `JNI_EEXIST`, a VM has already been created
+    AlreadyExists,
+    /// `JNI_EINVAL`, invalid arguments
+    InvalidArguments,
+    /// Any other negative (or unexpected positive) status
+    Other(jint),
+}
+
+impl JniError {
+    /// Returns `Ok` for `JNI_OK`, or else the error for `status`
+    pub const fn check(status: jint) -> Result<(), Self> {
+        match status {
+            JNI_OK => Ok(()),
+            status => Err(Self::from_status(status)),
+        }
+    }
+
+    /// Returns the error for a status that isn't `JNI_OK`
+    pub const fn from_status(status: jint) -> Self {
+        match status {
+            JNI_ERR => Self::Unknown,
+            JNI_EDETACHED => Self::Detached,
+            JNI_EVERSION => Self::Version,
+            JNI_ENOMEM => Self::NoMemory,
+            JNI_EEXIST => Self::AlreadyExists,
+            JNI_EINVAL => Self::InvalidArguments,
+            status => Self::Other(status),
+        }
+    }
+
+    /// Returns the status code of this error
+    pub const fn status(&self) -> jint {
+        match *self {
+            Self::Unknown => JNI_ERR,
+            Self::Detached => JNI_EDETACHED,
+            Self::Version => JNI_EVERSION,
+            Self::NoMemory => JNI_ENOMEM,
+            Self::AlreadyExists => JNI_EEXIST,
+            Self::InvalidArguments => JNI_EINVAL,
+            Self::Other(status) => status,
+        }
+    }
+}
+
+impl fmt::Display for JniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => f.write_str("unknown JNI error"),
+            Self::Detached => f.write_str("thread detached from the VM"),
+            Self::Version => f.write_str("JNI version error"),
+            Self::NoMemory => f.write_str("not enough memory"),
+            Self::AlreadyExists => f.write_str("VM already created"),
+            Self::InvalidArguments => f.write_str("invalid arguments"),
+            Self::Other(status) => write!(f, "JNI error {status}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JniError {}
+
+/// The functions of the invocation API
+#[derive(Clone, Copy, Debug)]
+pub struct InvocationApi {
+    pub JNI_GetDefaultJavaVMInitArgs: unsafe extern "system" fn(args: *mut c_void) -> jint,
+    pub JNI_CreateJavaVM: unsafe extern "system" fn(
+        pvm: *mut *mut JavaVM,
+        penv: *mut *mut c_void,
+        args: *mut c_void,
+    ) -> jint,
+    pub JNI_GetCreatedJavaVMs:
+        unsafe extern "system" fn(vmBuf: *mut *mut JavaVM, bufLen: jsize, nVMs: *mut jsize) -> jint,
+}
+
+#[cfg(not(feature = "dynamic"))]
+impl InvocationApi {
+    /// Returns the functions linked from `libjvm` at build time
+    pub const fn linked() -> Self {
+        Self {
+            JNI_GetDefaultJavaVMInitArgs: crate::JNI_GetDefaultJavaVMInitArgs,
+            JNI_CreateJavaVM: crate::JNI_CreateJavaVM,
+            JNI_GetCreatedJavaVMs: crate::JNI_GetCreatedJavaVMs,
+        }
+    }
+}
+
+impl InvocationApi {
+    /// Returns the number of VMs created in this process
+    ///
+    /// # Safety
+    ///
+    /// The functions must be the invocation API of a JVM library.
+    pub unsafe fn created_java_vm_count(&self) -> Result<usize, JniError> {
+        let mut count = 0;
+        JniError::check((self.JNI_GetCreatedJavaVMs)(ptr::null_mut(), 0, &mut count))?;
+        Ok(count.max(0) as usize)
+    }
+
+    /// Fills `buf` with the VMs created in this process and returns the filled part
+    ///
+    /// VMs that don't fit in `buf` are left out.
+    ///
+    /// # Safety
+    ///
+    /// The functions must be the invocation API of a JVM library.
+    pub unsafe fn created_java_vms_into<'a>(
+        &self,
+        buf: &'a mut [*mut JavaVM],
+    ) -> Result<&'a mut [*mut JavaVM], JniError> {
+        let len = jsize::try_from(buf.len()).unwrap_or(jsize::MAX);
+        let mut count = 0;
+        JniError::check((self.JNI_GetCreatedJavaVMs)(
+            buf.as_mut_ptr(),
+            len,
+            &mut count,
+        ))?;
+        let filled = count.clamp(0, len) as usize;
+        Ok(&mut buf[..filled])
+    }
+
+    /// Returns up to `N` of the VMs created in this process
+    ///
+    /// The JVM only supports one VM per process, so `N` would usually be 1.
+    ///
+    /// # Safety
+    ///
+    /// The functions must be the invocation API of a JVM library.
+    pub unsafe fn created_java_vms<const N: usize>(&self) -> Result<CreatedJavaVms<N>, JniError> {
+        let mut vms = [ptr::null_mut(); N];
+        let len = jsize::try_from(N).unwrap_or(jsize::MAX);
+        let mut total = 0;
+        JniError::check((self.JNI_GetCreatedJavaVMs)(
+            vms.as_mut_ptr(),
+            len,
+            &mut total,
+        ))?;
+        Ok(CreatedJavaVms {
+            vms,
+            len: total.clamp(0, len) as usize,
+            total: total.max(0) as usize,
+        })
+    }
+
+    /// Returns the first VM created in this process, if there is one
+    ///
+    /// # Safety
+    ///
+    /// The functions must be the invocation API of a JVM library.
+    pub unsafe fn created_java_vm(&self) -> Result<Option<*mut JavaVM>, JniError> {
+        Ok(self.created_java_vms::<1>()?.first())
+    }
+
+    /// Returns all of the VMs created in this process
+    ///
+    /// The VMs are counted first and then fetched, retrying if more were created in between.
+    ///
+    /// # Safety
+    ///
+    /// The functions must be the invocation API of a JVM library.
+    #[cfg(feature = "std")]
+    pub unsafe fn all_created_java_vms(&self) -> Result<std::vec::Vec<*mut JavaVM>, JniError> {
+        loop {
+            let count = self.created_java_vm_count()?;
+            let mut vms = std::vec![ptr::null_mut(); count];
+            let len = jsize::try_from(count).unwrap_or(jsize::MAX);
+            let mut total = 0;
+            JniError::check((self.JNI_GetCreatedJavaVMs)(
+                vms.as_mut_ptr(),
+                len,
+                &mut total,
+            ))?;
+            if total <= len {
+                vms.truncate(total.max(0) as usize);
+                return Ok(vms);
+            }
+        }
+    }
+}
+
+/// Options for [`InvocationApi::create_java_vm`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CreateOptions {
+    retry_older_versions: bool,
+    use_existing: bool,
+}
+
+impl CreateOptions {
+    /// Returns options that neither retry nor use an existing VM
+    pub const fn new() -> Self {
+        Self {
+            retry_older_versions: false,
+            use_existing: false,
+        }
+    }
+
+    /// Sets whether to retry with older [`JNI_VERSIONS`] when the requested version isn't
+    /// supported
+    pub const fn retry_older_versions(mut self, retry: bool) -> Self {
+        self.retry_older_versions = retry;
+        self
+    }
+
+    /// Sets whether to return the VM that was already created in the process instead of
+    /// failing with [`JniError::AlreadyExists`]
+    pub const fn use_existing(mut self, use_existing: bool) -> Self {
+        self.use_existing = use_existing;
+        self
+    }
+}
+
+/// A VM returned by [`InvocationApi::create_java_vm`], with the env of the current thread
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreatedJavaVm {
+    /// The VM
+    pub vm: *mut JavaVM,
+    /// The env of the current thread
+    pub env: *mut JNIEnv,
+    /// The JNI version reported by `GetVersion`
+    pub version: jint,
+    /// Whether the VM already existed, rather than being created by the call
+    pub existing: bool,
+    /// Whether the current thread was attached by the call, which is always the case for a
+    /// new VM
+    pub newly_attached: bool,
+}
+
+impl InvocationApi {
+    /// Creates a VM, and attaches the current thread to it
+    ///
+    /// The new VM is registered in the [`registry`].
+    /// With [`CreateOptions::retry_older_versions`], a `JNI_EVERSION` failure is retried with
+    /// each older version in [`JNI_VERSIONS`]. With [`CreateOptions::use_existing`], a
+    /// `JNI_EEXIST` failure falls back to the VM found with `JNI_GetCreatedJavaVMs` (or else the
+    /// registered VM), and the current thread is attached to it if needed. If neither finds a VM,
+    /// the failure is returned as [`JniError::AlreadyExists`].
+    ///
+    /// # Safety
+    ///
+    /// The functions must be the invocation API of a JVM library, and `args` must have valid
+    /// options.
+    pub unsafe fn create_java_vm(
+        &self,
+        args: &JavaVMInitArgs,
+        options: CreateOptions,
+    ) -> Result<CreatedJavaVm, JniError> {
+        let requested = args.version;
+        let mut args = *args;
+        let older = JNI_VERSIONS
+            .iter()
+            .copied()
+            .filter(|&version| version < requested);
+        let mut versions = Some(requested)
+            .into_iter()
+            .chain(older.filter(|_| options.retry_older_versions));
+        let status = loop {
+            let Some(version) = versions.next() else {
+                break JNI_EVERSION;
+            };
+            args.version = version;
+            let mut vm = ptr::null_mut();
+            let mut env = ptr::null_mut();
+            match (self.JNI_CreateJavaVM)(
+                &mut vm,
+                &mut env,
+                &mut args as *mut JavaVMInitArgs as *mut c_void,
+            ) {
+                JNI_OK => {
+                    let env: *mut JNIEnv = env.cast();
+                    let version = ((**env).v1_1.GetVersion)(env);
+                    registry::register_java_vm(vm, version);
+                    return Ok(CreatedJavaVm {
+                        vm,
+                        env,
+                        version,
+                        existing: false,
+                        newly_attached: true,
+                    });
+                }
+                JNI_EVERSION => continue,
+                status => break status,
+            }
+        };
+        if status != JNI_EEXIST || !options.use_existing {
+            return Err(JniError::from_status(status));
+        }
+
+        // Some VMs stop reporting themselves after refusing to create another
+        let vm = match self.created_java_vm()? {
+            Some(vm) => vm,
+            None => registry::java_vm().map_err(|_| JniError::AlreadyExists)?.vm,
+        };
+        let mut version = requested;
+        let (env, newly_attached) = loop {
+            match get_env_or_attach(vm, version, None)? {
+                EnvStatus::AlreadyAttached(env) => break (env, false),
+                EnvStatus::NewlyAttached(env) => break (env, true),
+                EnvStatus::UnsupportedVersion {
+                    highest: Some(highest),
+                } if options.retry_older_versions => version = highest,
+                EnvStatus::UnsupportedVersion { .. } => return Err(JniError::Version),
+            }
+        };
+        Ok(CreatedJavaVm {
+            vm,
+            env,
+            version: ((**env).v1_1.GetVersion)(env),
+            existing: true,
+            newly_attached,
+        })
+    }
+}
+
+/// Up to `N` VMs returned by [`InvocationApi::created_java_vms`]
+///
+/// Each VM is a `*mut JavaVM`, whose functions are called through the
+/// [`JNIInvokeInterface_`](crate::JNIInvokeInterface_) union, such as
+/// `(**vm).v1_2.GetEnv`.
+#[derive(Clone, Copy, Debug)]
+pub struct CreatedJavaVms<const N: usize> {
+    vms: [*mut JavaVM; N],
+    len: usize,
+    total: usize,
+}
+
+impl<const N: usize> CreatedJavaVms<N> {
+    /// Returns the VMs that were fetched
+    pub fn as_slice(&self) -> &[*mut JavaVM] {
+        &self.vms[..self.len]
+    }
+
+    /// Returns an iterator over the VMs that were fetched
+    pub fn iter(&self) -> slice::Iter<'_, *mut JavaVM> {
+        self.as_slice().iter()
+    }
+
+    /// Returns the first VM, if any
+    pub fn first(&self) -> Option<*mut JavaVM> {
+        self.as_slice().first().copied()
+    }
+
+    /// Returns the number of VMs that were fetched
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no VMs were fetched
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of VMs in the process, which may be more than were fetched
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns whether some VMs didn't fit in `N`
+    pub fn is_truncated(&self) -> bool {
+        self.total > self.len
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a CreatedJavaVms<N> {
+    type Item = &'a *mut JavaVM;
+    type IntoIter = slice::Iter<'a, *mut JavaVM>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const N: usize> IntoIterator for CreatedJavaVms<N> {
+    type Item = *mut JavaVM;
+    type IntoIter = core::iter::Take<core::array::IntoIter<*mut JavaVM, N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vms.into_iter().take(self.len)
+    }
+}
+
+impl JavaVMAttachArgs {
+    /// Returns arguments for attaching a thread with the given JNI version, thread name and
+    /// thread group
+    ///
+    /// The arguments point at `name`, which must outlive any use of them. `group` must be a
+    /// global reference to a `java.lang.ThreadGroup`, or `None` for the main thread group.
+    pub fn new(version: jint, name: &CStr, group: Option<jobject>) -> Self {
+        Self {
+            version,
+            name: name.as_ptr().cast_mut(),
+            group: group.unwrap_or(ptr::null_mut()),
+        }
+    }
+}
+
+/// The env of a thread attached by [`attach_current_thread`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttachedEnv {
+    /// The env of the current thread
+    pub env: *mut JNIEnv,
+    /// Whether the thread was attached by this call, and so should be detached by the caller
+    pub newly_attached: bool,
+}
+
+/// Attaches the current thread to `vm`, or returns its env if it's already attached
+///
+/// `version` is the JNI version supported by `vm`, such as the result of `GetVersion`. `args`
+/// give the name and group of an attached thread, and their version is replaced with `version`.
+/// Whether the thread was already attached is checked with `GetEnv`, which needs JNI 1.2. With
+/// an older VM the thread is always reported as newly attached.
+///
+/// # Safety
+///
+/// `vm` must be a valid VM that supports `version`, and `args`, if given, must have a valid
+/// `name` and `group`.
+pub unsafe fn attach_current_thread(
+    vm: *mut JavaVM,
+    version: jint,
+    args: Option<&JavaVMAttachArgs>,
+) -> Result<AttachedEnv, JniError> {
+    attach(vm, version, args, (**vm).v1_1.AttachCurrentThread)
+}
+
+/// Attaches the current thread to `vm` as a daemon thread, or returns its env if it's already
+/// attached
+///
+/// `AttachCurrentThreadAsDaemon` was added in JNI 1.4, so this returns [`JniError::Version`]
+/// without calling anything if `version` is older. Otherwise this behaves like
+/// [`attach_current_thread`].
+///
+/// # Safety
+///
+/// `vm` must be a valid VM that supports `version`, and `args`, if given, must have a valid
+/// `name` and `group`.
+pub unsafe fn attach_current_thread_as_daemon(
+    vm: *mut JavaVM,
+    version: jint,
+    args: Option<&JavaVMAttachArgs>,
+) -> Result<AttachedEnv, JniError> {
+    if version < JNI_VERSION_1_4 {
+        return Err(JniError::Version);
+    }
+    attach(vm, version, args, (**vm).v1_4.AttachCurrentThreadAsDaemon)
+}
+
+/// Returns `args` with their version replaced by `version`, the version supported by the VM
+///
+/// The VM rejects arguments with a version it doesn't support with `JNI_EVERSION`.
+fn attach_args(version: jint, args: &JavaVMAttachArgs) -> JavaVMAttachArgs {
+    JavaVMAttachArgs { version, ..*args }
+}
+
+type AttachFn = unsafe extern "system" fn(*mut JavaVM, *mut *mut c_void, *mut c_void) -> jint;
+
+unsafe fn attach(
+    vm: *mut JavaVM,
+    version: jint,
+    args: Option<&JavaVMAttachArgs>,
+    attach: AttachFn,
+) -> Result<AttachedEnv, JniError> {
+    let mut env: *mut c_void = ptr::null_mut();
+    if version >= JNI_VERSION_1_2 {
+        match ((**vm).v1_2.GetEnv)(vm, &mut env, JNI_VERSION_1_2) {
+            JNI_OK => {
+                return Ok(AttachedEnv {
+                    env: env.cast(),
+                    newly_attached: false,
+                })
+            }
+            JNI_EDETACHED => {}
+            status => return Err(JniError::from_status(status)),
+        }
+    }
+    let mut args = args.map(|args| attach_args(version, args));
+    let args = args.as_mut().map_or(ptr::null_mut(), |args| {
+        args as *mut JavaVMAttachArgs as *mut c_void
+    });
+    JniError::check(attach(vm, &mut env, args))?;
+    Ok(AttachedEnv {
+        env: env.cast(),
+        newly_attached: true,
+    })
+}
+
+/// The outcome of [`get_env_or_attach`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvStatus {
+    /// The thread was already attached and has this env
+    AlreadyAttached(*mut JNIEnv),
+    /// The thread was attached by the call and has this env, and should be detached by the
+    /// caller
+    NewlyAttached(*mut JNIEnv),
+    /// The VM doesn't support the requested version, and the thread is left as it was
+    UnsupportedVersion {
+        /// The newest of [`JNI_VERSIONS`] older than the requested version that the VM
+        /// supports, if any
+        highest: Option<jint>,
+    },
+}
+
+/// Returns the env of the current thread for `version`, attaching the thread if needed
+///
+/// This calls `GetEnv`, and `AttachCurrentThread` if the thread is detached. `args` give the
+/// name and group of an attached thread, and their version is replaced with `version`.
+///
+/// If `version` isn't supported, [`JNI_VERSIONS`] are probed downwards with `GetEnv` for the
+/// newest supported version. A detached thread is attached for the probe and then detached
+/// again.
+///
+/// # Safety
+///
+/// `vm` must be a valid VM that supports at least JNI 1.2, and `args`, if given, must have a
+/// valid `name` and `group`.
+pub unsafe fn get_env_or_attach(
+    vm: *mut JavaVM,
+    version: jint,
+    args: Option<&JavaVMAttachArgs>,
+) -> Result<EnvStatus, JniError> {
+    let mut env: *mut c_void = ptr::null_mut();
+    match ((**vm).v1_2.GetEnv)(vm, &mut env, version) {
+        JNI_OK => return Ok(EnvStatus::AlreadyAttached(env.cast())),
+        JNI_EVERSION => {
+            return Ok(EnvStatus::UnsupportedVersion {
+                highest: highest_version_below(vm, version),
+            })
+        }
+        JNI_EDETACHED => {}
+        status => return Err(JniError::from_status(status)),
+    }
+
+    let mut args = attach_args(
+        version,
+        args.unwrap_or(&JavaVMAttachArgs {
+            version,
+            name: ptr::null_mut(),
+            group: ptr::null_mut(),
+        }),
+    );
+    let args = &mut args as *mut JavaVMAttachArgs as *mut c_void;
+    match ((**vm).v1_1.AttachCurrentThread)(vm, &mut env, args) {
+        JNI_OK => {}
+        JNI_EVERSION => {
+            JniError::check(((**vm).v1_1.AttachCurrentThread)(
+                vm,
+                &mut env,
+                ptr::null_mut(),
+            ))?;
+            let highest = highest_version_below(vm, version);
+            detach_current_thread(vm)?;
+            return Ok(EnvStatus::UnsupportedVersion { highest });
+        }
+        status => return Err(JniError::from_status(status)),
+    }
+    // Some VMs don't check the version when attaching
+    match ((**vm).v1_2.GetEnv)(vm, &mut env, version) {
+        JNI_OK => Ok(EnvStatus::NewlyAttached(env.cast())),
+        JNI_EVERSION => {
+            let highest = highest_version_below(vm, version);
+            detach_current_thread(vm)?;
+            Ok(EnvStatus::UnsupportedVersion { highest })
+        }
+        status => {
+            let _ = detach_current_thread(vm);
+            Err(JniError::from_status(status))
+        }
+    }
+}
+
+/// Probes an attached thread for the newest of [`JNI_VERSIONS`] older than `version`
+unsafe fn highest_version_below(vm: *mut JavaVM, version: jint) -> Option<jint> {
+    JNI_VERSIONS
+        .iter()
+        .copied()
+        .filter(|&known| known < version)
+        .find(|&known| {
+            let mut env = ptr::null_mut();
+            ((**vm).v1_2.GetEnv)(vm, &mut env, known) == JNI_OK
+        })
+}
+
+/// Detaches the current thread from `vm`
+///
+/// # Safety
+///
+/// `vm` must be a valid VM, and the current thread must not have Java frames on its stack.
+/// Every local reference of the thread is freed.
+pub unsafe fn detach_current_thread(vm: *mut JavaVM) -> Result<(), JniError> {
+    JniError::check(((**vm).v1_1.DetachCurrentThread)(vm))
+}
+
+/// The last VM destroyed by [`destroy_java_vm`]
+static DESTROYED_VM: AtomicPtr<JavaVM> = AtomicPtr::new(ptr::null_mut());
+
+/// Destroys `vm` with `DestroyJavaVM`
+///
+/// `DestroyJavaVM` waits until the current thread is the last non-daemon thread. Once it
+/// returns, the VM is unregistered from the [`registry`] if it was registered, and threads
+/// attached with `attach_current_thread_until_exit` (with `std`) no longer detach from it when
+/// they exit.
+///
+/// # Safety
+///
+/// `vm` must be a valid VM, which is unusable once this returns `Ok`.
+pub unsafe fn destroy_java_vm(vm: *mut JavaVM) -> Result<(), JniError> {
+    JniError::check(((**vm).v1_1.DestroyJavaVM)(vm))?;
+    DESTROYED_VM.store(vm, Ordering::Release);
+    registry::unregister_destroyed_java_vm(vm);
+    Ok(())
+}
+
+/// Returns whether `vm` was destroyed by [`destroy_java_vm`]
+#[cfg(feature = "std")]
+fn is_destroyed(vm: *mut JavaVM) -> bool {
+    DESTROYED_VM.load(Ordering::Acquire) == vm
+}
+
+#[cfg(feature = "std")]
+pub use self::thread::{attach_current_thread_until_exit, set_detach_on_exit};
+
+#[cfg(feature = "std")]
+mod thread {
+    use std::cell::RefCell;
+
+    use super::{attach_current_thread, detach_current_thread, is_destroyed, JniError};
+    use crate::{jint, JNIEnv, JavaVM, JavaVMAttachArgs};
+
+    struct Attachment {
+        vm: *mut JavaVM,
+        env: *mut JNIEnv,
+        detach: bool,
+    }
+
+    impl Drop for Attachment {
+        fn drop(&mut self) {
+            // A destroyed VM has already detached its threads
+            if self.detach && !is_destroyed(self.vm) {
+                // Nothing can be done about an error while the thread exits
+                let _ = unsafe { detach_current_thread(self.vm) };
+            }
+        }
+    }
+
+    std::thread_local! {
+        static ATTACHMENT: RefCell<Option<Attachment>> = const { RefCell::new(None) };
+    }
+
+    /// Returns the env of the current thread, attaching it to `vm` on first use and detaching
+    /// it when the thread exits
+    ///
+    /// The env is cached in a thread local, so later calls on the same thread return it
+    /// without calling into the VM. A thread that was already attached, such as one created by
+    /// the JVM, isn't detached on exit. `args` are only used when the thread is attached.
+    ///
+    /// The cache holds one VM per thread (a process can only create one), and a call with a
+    /// different VM returns [`JniError::InvalidArguments`]. Thread locals may not be destroyed
+    /// on the main thread, which is then left attached.
+    ///
+    /// A thread that exits after its VM was destroyed by
+    /// [`destroy_java_vm`](super::destroy_java_vm) isn't detached.
+    ///
+    /// # Safety
+    ///
+    /// Besides the requirements of [`attach_current_thread`], the thread must not be detached
+    /// other than by this function's thread local destructor while it is cached. If the VM is
+    /// destroyed other than by `destroy_java_vm`, it must outlive the thread, or detaching must
+    /// be turned off with [`set_detach_on_exit`] first.
+    pub unsafe fn attach_current_thread_until_exit(
+        vm: *mut JavaVM,
+        version: jint,
+        args: Option<&JavaVMAttachArgs>,
+    ) -> Result<*mut JNIEnv, JniError> {
+        ATTACHMENT.with(|attachment| {
+            if let Some(attachment) = &*attachment.borrow() {
+                return if attachment.vm == vm {
+                    Ok(attachment.env)
+                } else {
+                    Err(JniError::InvalidArguments)
+                };
+            }
+            let attached = attach_current_thread(vm, version, args)?;
+            *attachment.borrow_mut() = Some(Attachment {
+                vm,
+                env: attached.env,
+                detach: attached.newly_attached,
+            });
+            Ok(attached.env)
+        })
+    }
+
+    /// Sets whether the thread attached by [`attach_current_thread_until_exit`] is detached
+    /// when it exits
+    ///
+    /// Returns `false` if the current thread has no cached env. This can opt out of detaching
+    /// a thread that will be detached by other means, or that outlives Rust's thread locals.
+    pub fn set_detach_on_exit(detach: bool) -> bool {
+        ATTACHMENT.with(|attachment| match &mut *attachment.borrow_mut() {
+            Some(attachment) => {
+                attachment.detach = detach;
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+#[cfg(feature = "dynamic")]
+pub use self::dynamic::{LoadError, LoaderMessage};
+
+#[cfg(feature = "dynamic")]
+mod dynamic {
+    use core::ffi::{c_char, c_void, CStr};
+    use core::fmt;
+    use core::mem;
+    use core::str;
+
+    use super::InvocationApi;
+    use crate::{jint, jsize, JavaVM};
+
+    /// Error returned when the JVM library can't be loaded
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum LoadError {
+        /// The library could not be opened
+        Open(LoaderMessage),
+        /// The library doesn't export one of the invocation API functions
+        MissingSymbol(&'static str),
+    }
+
+    impl fmt::Display for LoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Open(message) => write!(f, "failed to load the JVM library: {message}"),
+                Self::MissingSymbol(symbol) => {
+                    write!(f, "the JVM library doesn't export `{symbol}`")
+                }
+            }
+        }
+    }
+
+    const MESSAGE_CAPACITY: usize = 112;
+
+    /// The reason given by the system loader for failing to open a library
+    ///
+    /// Long messages are truncated at the start, since the path being loaded usually comes
+    /// before the reason.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct LoaderMessage {
+        buf: [u8; MESSAGE_CAPACITY],
+        len: u8,
+    }
+
+    impl LoaderMessage {
+        fn new(message: &[u8]) -> Self {
+            let mut buf = [0; MESSAGE_CAPACITY];
+            let len = if message.len() > MESSAGE_CAPACITY {
+                buf[..3].copy_from_slice(b"...");
+                buf[3..].copy_from_slice(&message[message.len() - (MESSAGE_CAPACITY - 3)..]);
+                MESSAGE_CAPACITY
+            } else {
+                buf[..message.len()].copy_from_slice(message);
+                message.len()
+            };
+            Self {
+                buf,
+                len: len as u8,
+            }
+        }
+
+        /// Returns the message as bytes, in the platform's encoding
+        pub fn as_bytes(&self) -> &[u8] {
+            &self.buf[..usize::from(self.len)]
+        }
+    }
+
+    impl fmt::Display for LoaderMessage {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut bytes = self.as_bytes();
+            loop {
+                match str::from_utf8(bytes) {
+                    Ok(valid) => return f.write_str(valid),
+                    Err(err) => {
+                        let (valid, rest) = bytes.split_at(err.valid_up_to());
+                        f.write_str(str::from_utf8(valid).map_err(|_| fmt::Error)?)?;
+                        f.write_str("\u{FFFD}")?;
+                        bytes = &rest[err.error_len().unwrap_or(rest.len())..];
+                    }
+                }
+            }
+        }
+    }
+
+    impl fmt::Debug for LoaderMessage {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{:?}", format_args!("{self}"))
+        }
+    }
+
+    impl InvocationApi {
+        /// Loads the JVM library at `path` and looks up the invocation API functions
+        ///
+        /// `path` would usually be a `libjvm.so` (`jvm.dll`, `libjvm.dylib`) found under a
+        /// JDK's `lib/server` directory. The library is never unloaded, since a Java VM can't
+        /// be unloaded once it has been created.
+        ///
+        /// # Safety
+        ///
+        /// Loading a library runs its initialization code, so `path` must name a JVM library
+        /// that is compatible with the current process.
+        pub unsafe fn load(path: &CStr) -> Result<Self, LoadError> {
+            let library = sys::open(path)?;
+            let get_default_args: unsafe extern "system" fn(*mut c_void) -> jint =
+                mem::transmute(symbol(library, "JNI_GetDefaultJavaVMInitArgs\0")?);
+            let create: unsafe extern "system" fn(
+                *mut *mut JavaVM,
+                *mut *mut c_void,
+                *mut c_void,
+            ) -> jint = mem::transmute(symbol(library, "JNI_CreateJavaVM\0")?);
+            let get_created: unsafe extern "system" fn(
+                *mut *mut JavaVM,
+                jsize,
+                *mut jsize,
+            ) -> jint = mem::transmute(symbol(library, "JNI_GetCreatedJavaVMs\0")?);
+            Ok(Self {
+                JNI_GetDefaultJavaVMInitArgs: get_default_args,
+                JNI_CreateJavaVM: create,
+                JNI_GetCreatedJavaVMs: get_created,
+            })
+        }
+    }
+
+    /// Looks up `name`, which must be NUL terminated
+    unsafe fn symbol(library: *mut c_void, name: &'static str) -> Result<*mut c_void, LoadError> {
+        let address = sys::symbol(library, name.as_ptr().cast());
+        if address.is_null() {
+            Err(LoadError::MissingSymbol(name.trim_end_matches('\0')))
+        } else {
+            Ok(address)
+        }
+    }
+
+    #[cfg(unix)]
+    mod sys {
+        use super::*;
+
+        pub(super) unsafe fn open(path: &CStr) -> Result<*mut c_void, LoadError> {
+            let library = libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_GLOBAL);
+            if library.is_null() {
+                let message = libc::dlerror();
+                let message = if message.is_null() {
+                    &[][..]
+                } else {
+                    CStr::from_ptr(message).to_bytes()
+                };
+                Err(LoadError::Open(LoaderMessage::new(message)))
+            } else {
+                Ok(library)
+            }
+        }
+
+        pub(super) unsafe fn symbol(library: *mut c_void, name: *const c_char) -> *mut c_void {
+            libc::dlsym(library, name)
+        }
+    }
+
+    #[cfg(windows)]
+    mod sys {
+        use super::*;
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn LoadLibraryA(lpLibFileName: *const c_char) -> *mut c_void;
+            fn GetProcAddress(hModule: *mut c_void, lpProcName: *const c_char) -> *mut c_void;
+            fn GetLastError() -> u32;
+        }
+
+        pub(super) unsafe fn open(path: &CStr) -> Result<*mut c_void, LoadError> {
+            let library = LoadLibraryA(path.as_ptr());
+            if library.is_null() {
+                let mut message = [0u8; 32];
+                let len = format_error_code(&mut message, GetLastError());
+                Err(LoadError::Open(LoaderMessage::new(&message[..len])))
+            } else {
+                Ok(library)
+            }
+        }
+
+        pub(super) unsafe fn symbol(library: *mut c_void, name: *const c_char) -> *mut c_void {
+            GetProcAddress(library, name)
+        }
+
+        fn format_error_code(buf: &mut [u8; 32], code: u32) -> usize {
+            use core::fmt::Write;
+
+            struct Cursor<'a>(&'a mut [u8], usize);
+            impl Write for Cursor<'_> {
+                fn write_str(&mut self, s: &str) -> fmt::Result {
+                    let end = self.1 + s.len();
+                    self.0
+                        .get_mut(self.1..end)
+                        .ok_or(fmt::Error)?
+                        .copy_from_slice(s.as_bytes());
+                    self.1 = end;
+                    Ok(())
+                }
+            }
+
+            let mut cursor = Cursor(buf, 0);
+            let _ = write!(cursor, "error code {code}");
+            cursor.1
+        }
+    }
+}
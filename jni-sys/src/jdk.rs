@@ -0,0 +1,344 @@
+//! Locating installed JDKs and their JVM libraries
+//!
+//! [`JdkSearch`] looks for JDKs in `JAVA_HOME`, next to the `java` found on `PATH` and under
+//! conventional install roots such as `/usr/lib/jvm`. Each [`Jdk`] found reports its `release`
+//! file and the JVM libraries of each VM variant (`server`, `client`, ...) it ships.
+//!
+//! This module is only available with the `std` feature.
+
+use std::cmp::Reverse;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Directories that Linux distributions and vendors conventionally install JDKs under
+pub const LINUX_INSTALL_ROOTS: &[&str] = &[
+    "/usr/lib/jvm",
+    "/usr/lib64/jvm",
+    "/usr/java",
+    "/opt/java",
+    "/opt/jdk",
+];
+
+/// File names of the JVM library on the supported platforms
+const LIBJVM_NAMES: &[&str] = &["libjvm.so", "libjvm.dylib", "jvm.dll"];
+
+/// The VM variants to prefer, in order, when one isn't named
+const PREFERRED_VARIANTS: &[&str] = &["server", "client"];
+
+/// Where a JDK was found
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JdkSource {
+    /// The `JAVA_HOME` environment variable
+    JavaHome,
+    /// The `java` executable found on `PATH`
+    Path,
+    /// A directory under an install root
+    InstallRoot,
+}
+
+/// The contents of a JDK's `release` file
+///
+/// The file is a list of `KEY="value"` lines describing the JDK build.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Release {
+    entries: Vec<(String, String)>,
+}
+
+impl Release {
+    /// Parses the contents of a `release` file
+    ///
+    /// Blank lines, comments and lines without `=` are skipped.
+    pub fn parse(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+                Some((key.trim().to_owned(), value.to_owned()))
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Reads and parses a `release` file
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path).map(|contents| Self::parse(&contents))
+    }
+
+    /// Returns the value of `key`, if present
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all entries in file order
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns `JAVA_VERSION`, such as `"17.0.9"` or `"1.8.0_392"`
+    pub fn java_version(&self) -> Option<&str> {
+        self.get("JAVA_VERSION")
+    }
+
+    /// Returns `JAVA_RUNTIME_VERSION`, such as `"17.0.9+9"`
+    pub fn java_runtime_version(&self) -> Option<&str> {
+        self.get("JAVA_RUNTIME_VERSION")
+    }
+
+    /// Returns `IMPLEMENTOR`, such as `"Eclipse Adoptium"`
+    pub fn implementor(&self) -> Option<&str> {
+        self.get("IMPLEMENTOR")
+    }
+
+    /// Returns the names of the modules listed in `MODULES`
+    pub fn modules(&self) -> impl Iterator<Item = &str> {
+        self.get("MODULES").unwrap_or_default().split_whitespace()
+    }
+
+    /// Returns the feature release number of `JAVA_VERSION`, such as 17 or 8 (for `1.8.0`)
+    pub fn feature_version(&self) -> Option<u32> {
+        let mut parts = self.java_version()?.split(|c: char| !c.is_ascii_digit());
+        match parts.next()?.parse().ok()? {
+            1 => parts.next()?.parse().ok(),
+            feature => Some(feature),
+        }
+    }
+}
+
+/// A JVM library of a JDK
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Libjvm {
+    /// The VM variant, which is the name of the directory containing the library
+    pub variant: String,
+    /// The path to the library
+    pub path: PathBuf,
+}
+
+/// An installed JDK (or JRE)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Jdk {
+    /// The JDK's home directory, which is what `JAVA_HOME` would be set to
+    pub home: PathBuf,
+    /// Where the JDK was found
+    pub source: JdkSource,
+    /// The parsed `release` file, if the JDK has one
+    pub release: Option<Release>,
+}
+
+impl Jdk {
+    /// Checks that `home` looks like a JDK and reads its `release` file
+    ///
+    /// A directory is accepted if it has a `release` file, a `bin/java` executable or a JVM
+    /// library.
+    pub fn at(home: impl Into<PathBuf>, source: JdkSource) -> Option<Self> {
+        let home = home.into();
+        if !home.is_dir() {
+            return None;
+        }
+        let release = Release::read(home.join("release")).ok();
+        let mut jdk = Self {
+            home,
+            source,
+            release,
+        };
+        let has_java = ["bin/java", "bin/java.exe"]
+            .iter()
+            .any(|java| jdk.home.join(java).is_file());
+        if jdk.release.is_none() && !has_java && jdk.libjvms().is_empty() {
+            return None;
+        }
+        if let Ok(home) = jdk.home.canonicalize() {
+            jdk.home = home;
+        }
+        Some(jdk)
+    }
+
+    /// Returns the JVM libraries of every VM variant in the JDK
+    ///
+    /// This covers the layout of JDK 9 and later (`lib/<variant>`, or `bin/<variant>` on
+    /// Windows) as well as the JDK 8 `jre` layout.
+    pub fn libjvms(&self) -> Vec<Libjvm> {
+        let mut parents = vec![
+            self.home.join("lib"),
+            self.home.join("bin"),
+            self.home.join("jre/lib"),
+            self.home.join("jre/bin"),
+        ];
+        // JDK 8 on Linux puts the variants under an architecture directory
+        parents.extend(subdirectories(&self.home.join("jre/lib")));
+
+        let mut libjvms = vec![];
+        for parent in parents {
+            for dir in subdirectories(&parent) {
+                let Some(variant) = dir.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                for name in LIBJVM_NAMES {
+                    let path = dir.join(name);
+                    if path.is_file() {
+                        libjvms.push(Libjvm {
+                            variant: variant.to_owned(),
+                            path,
+                        });
+                    }
+                }
+            }
+        }
+        libjvms
+    }
+
+    /// Returns the JVM library of the named VM variant, such as `"server"`
+    pub fn libjvm(&self, variant: &str) -> Option<Libjvm> {
+        self.libjvms()
+            .into_iter()
+            .find(|libjvm| libjvm.variant == variant)
+    }
+
+    /// Returns the `server` JVM library, or else the `client` one, or else any other
+    pub fn default_libjvm(&self) -> Option<Libjvm> {
+        let libjvms = self.libjvms();
+        PREFERRED_VARIANTS
+            .iter()
+            .find_map(|variant| libjvms.iter().find(|libjvm| libjvm.variant == *variant))
+            .or_else(|| libjvms.first())
+            .cloned()
+    }
+}
+
+/// Where to look for JDKs
+///
+/// The search is configured explicitly so it can be pointed at any directory tree, or from the
+/// process environment with [`JdkSearch::from_env`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JdkSearch {
+    java_home: Option<PathBuf>,
+    path: Option<OsString>,
+    install_roots: Vec<PathBuf>,
+}
+
+impl JdkSearch {
+    /// Returns a search that looks nowhere
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a search of `JAVA_HOME`, `PATH` and, on Linux, the [`LINUX_INSTALL_ROOTS`]
+    pub fn from_env() -> Self {
+        let mut search = Self::new();
+        if let Some(java_home) = std::env::var_os("JAVA_HOME").filter(|home| !home.is_empty()) {
+            search = search.java_home(java_home);
+        }
+        if let Some(path) = std::env::var_os("PATH") {
+            search = search.path(path);
+        }
+        if cfg!(target_os = "linux") {
+            for root in LINUX_INSTALL_ROOTS {
+                search = search.install_root(root);
+            }
+        }
+        search
+    }
+
+    /// Sets the JDK home to check first
+    pub fn java_home(mut self, java_home: impl Into<PathBuf>) -> Self {
+        self.java_home = Some(java_home.into());
+        self
+    }
+
+    /// Sets the `PATH`-style list of directories to look for `java` in
+    pub fn path(mut self, path: impl Into<OsString>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Adds a directory whose subdirectories are checked for JDKs
+    pub fn install_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.install_roots.push(root.into());
+        self
+    }
+
+    /// Returns the JDKs found, without duplicates, in order of `JAVA_HOME`, `PATH` and then
+    /// the install roots
+    ///
+    /// JDKs under an install root are sorted by name.
+    pub fn find(&self) -> Vec<Jdk> {
+        let mut jdks: Vec<Jdk> = vec![];
+        let mut push = |jdk: Option<Jdk>| {
+            if let Some(jdk) = jdk {
+                if !jdks.iter().any(|found| found.home == jdk.home) {
+                    jdks.push(jdk);
+                }
+            }
+        };
+
+        if let Some(java_home) = &self.java_home {
+            push(Jdk::at(java_home, JdkSource::JavaHome));
+        }
+        if let Some(path) = &self.path {
+            for dir in std::env::split_paths(path) {
+                for name in ["java", "java.exe"] {
+                    let java = dir.join(name);
+                    if !java.is_file() {
+                        continue;
+                    }
+                    // Follow links such as /usr/bin/java -> /etc/alternatives/java -> ...
+                    let home = java
+                        .canonicalize()
+                        .ok()
+                        .and_then(|java| Some(java.parent()?.parent()?.to_path_buf()));
+                    push(home.and_then(|home| Jdk::at(home, JdkSource::Path)));
+                }
+            }
+        }
+        for root in &self.install_roots {
+            let mut homes = subdirectories(root);
+            homes.sort();
+            for home in homes {
+                push(Jdk::at(home, JdkSource::InstallRoot));
+            }
+        }
+        jdks
+    }
+
+    /// Returns the default JVM library of the first JDK from `JAVA_HOME` or the `PATH` that has
+    /// one, or else of the newest JDK under the install roots
+    ///
+    /// JDKs under the install roots are ordered by [`Release::feature_version`], newest first,
+    /// with those of unknown version last.
+    pub fn default_libjvm(&self) -> Option<Libjvm> {
+        let mut jdks = self.find();
+        // The sort is stable, so `JAVA_HOME` and `PATH` keep their order
+        jdks.sort_by_key(|jdk| match jdk.source {
+            JdkSource::InstallRoot => {
+                let version = jdk.release.as_ref().and_then(Release::feature_version);
+                (1, Reverse(version))
+            }
+            _ => (0, Reverse(None)),
+        });
+        jdks.iter().find_map(Jdk::default_libjvm)
+    }
+}
+
+/// Returns the directories (or links to directories) in `dir`
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_dir())
+        .collect()
+}
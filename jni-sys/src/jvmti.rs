@@ -0,0 +1,1779 @@
+//! Raw bindings to the JVM Tool Interface (JVMTI), from `jvmti.h`
+//!
+//! An agent gets a [`jvmtiEnv`] from `GetEnv` with one of the `JVMTI_VERSION_*` constants. Like
+//! the JNI function table, [`jvmtiInterface_1_`] is a union with a member per JVMTI version, so
+//! functions added after JVMTI 1.0 are only reachable through the version that added them.
+
+use core::ffi::{c_char, c_uchar, c_uint, c_void};
+
+use jni_sys_macros::jni_to_union;
+
+use crate::{
+    jboolean, jchar, jclass, jdouble, jfieldID, jfloat, jint, jlong, jmethodID, jobject, jvalue,
+    JNIEnv, JNINativeInterface_,
+};
+
+#[cfg(not(ctest))]
+pub mod agent;
+#[cfg(not(ctest))]
+pub mod capabilities;
+#[cfg(not(ctest))]
+pub mod errors;
+#[cfg(not(ctest))]
+pub mod events;
+#[cfg(not(ctest))]
+pub mod extensions;
+#[cfg(not(ctest))]
+pub mod heap;
+#[cfg(not(ctest))]
+pub mod memory;
+
+pub const JVMTI_VERSION_1: jint = 0x30010000;
+pub const JVMTI_VERSION_1_0: jint = 0x30010000;
+pub const JVMTI_VERSION_1_1: jint = 0x30010100;
+pub const JVMTI_VERSION_1_2: jint = 0x30010200;
+pub const JVMTI_VERSION_9: jint = 0x30090000;
+pub const JVMTI_VERSION_11: jint = 0x300B0000;
+pub const JVMTI_VERSION_19: jint = 0x30130000;
+pub const JVMTI_VERSION_21: jint = 0x30150000;
+
+pub type jthread = jobject;
+pub type jthreadGroup = jobject;
+pub type jlocation = jlong;
+
+#[derive(Debug)]
+pub enum _jrawMonitorID {}
+pub type jrawMonitorID = *mut _jrawMonitorID;
+
+pub type jniNativeInterface = JNINativeInterface_;
+
+pub type jvmtiEnv = *const jvmtiInterface_1_;
+
+// Thread State Flags
+
+pub const JVMTI_THREAD_STATE_ALIVE: jint = 0x0001;
+pub const JVMTI_THREAD_STATE_TERMINATED: jint = 0x0002;
+pub const JVMTI_THREAD_STATE_RUNNABLE: jint = 0x0004;
+pub const JVMTI_THREAD_STATE_BLOCKED_ON_MONITOR_ENTER: jint = 0x0400;
+pub const JVMTI_THREAD_STATE_WAITING: jint = 0x0080;
+pub const JVMTI_THREAD_STATE_WAITING_INDEFINITELY: jint = 0x0010;
+pub const JVMTI_THREAD_STATE_WAITING_WITH_TIMEOUT: jint = 0x0020;
+pub const JVMTI_THREAD_STATE_SLEEPING: jint = 0x0040;
+pub const JVMTI_THREAD_STATE_IN_OBJECT_WAIT: jint = 0x0100;
+pub const JVMTI_THREAD_STATE_PARKED: jint = 0x0200;
+pub const JVMTI_THREAD_STATE_SUSPENDED: jint = 0x100000;
+pub const JVMTI_THREAD_STATE_INTERRUPTED: jint = 0x200000;
+pub const JVMTI_THREAD_STATE_IN_NATIVE: jint = 0x400000;
+pub const JVMTI_THREAD_STATE_VENDOR_1: jint = 0x10000000;
+pub const JVMTI_THREAD_STATE_VENDOR_2: jint = 0x20000000;
+pub const JVMTI_THREAD_STATE_VENDOR_3: jint = 0x40000000;
+
+// java.lang.Thread.State Conversion Masks
+
+pub const JVMTI_JAVA_LANG_THREAD_STATE_MASK: jint = JVMTI_THREAD_STATE_TERMINATED
+    | JVMTI_THREAD_STATE_ALIVE
+    | JVMTI_THREAD_STATE_RUNNABLE
+    | JVMTI_THREAD_STATE_BLOCKED_ON_MONITOR_ENTER
+    | JVMTI_THREAD_STATE_WAITING
+    | JVMTI_THREAD_STATE_WAITING_INDEFINITELY
+    | JVMTI_THREAD_STATE_WAITING_WITH_TIMEOUT;
+pub const JVMTI_JAVA_LANG_THREAD_STATE_NEW: jint = 0;
+pub const JVMTI_JAVA_LANG_THREAD_STATE_TERMINATED: jint = JVMTI_THREAD_STATE_TERMINATED;
+pub const JVMTI_JAVA_LANG_THREAD_STATE_RUNNABLE: jint =
+    JVMTI_THREAD_STATE_ALIVE | JVMTI_THREAD_STATE_RUNNABLE;
+pub const JVMTI_JAVA_LANG_THREAD_STATE_BLOCKED: jint =
+    JVMTI_THREAD_STATE_ALIVE | JVMTI_THREAD_STATE_BLOCKED_ON_MONITOR_ENTER;
+pub const JVMTI_JAVA_LANG_THREAD_STATE_WAITING: jint =
+    JVMTI_THREAD_STATE_ALIVE | JVMTI_THREAD_STATE_WAITING | JVMTI_THREAD_STATE_WAITING_INDEFINITELY;
+pub const JVMTI_JAVA_LANG_THREAD_STATE_TIMED_WAITING: jint =
+    JVMTI_THREAD_STATE_ALIVE | JVMTI_THREAD_STATE_WAITING | JVMTI_THREAD_STATE_WAITING_WITH_TIMEOUT;
+
+// Thread Priority Constants
+
+pub const JVMTI_THREAD_MIN_PRIORITY: jint = 1;
+pub const JVMTI_THREAD_NORM_PRIORITY: jint = 5;
+pub const JVMTI_THREAD_MAX_PRIORITY: jint = 10;
+
+// Heap Filter Flags
+
+pub const JVMTI_HEAP_FILTER_TAGGED: jint = 0x4;
+pub const JVMTI_HEAP_FILTER_UNTAGGED: jint = 0x8;
+pub const JVMTI_HEAP_FILTER_CLASS_TAGGED: jint = 0x10;
+pub const JVMTI_HEAP_FILTER_CLASS_UNTAGGED: jint = 0x20;
+
+// Heap Visit Control Flags
+
+pub const JVMTI_VISIT_OBJECTS: jint = 0x100;
+pub const JVMTI_VISIT_ABORT: jint = 0x8000;
+
+// Heap Reference Enumeration
+
+pub type jvmtiHeapReferenceKind = jint;
+
+pub const JVMTI_HEAP_REFERENCE_CLASS: jvmtiHeapReferenceKind = 1;
+pub const JVMTI_HEAP_REFERENCE_FIELD: jvmtiHeapReferenceKind = 2;
+pub const JVMTI_HEAP_REFERENCE_ARRAY_ELEMENT: jvmtiHeapReferenceKind = 3;
+pub const JVMTI_HEAP_REFERENCE_CLASS_LOADER: jvmtiHeapReferenceKind = 4;
+pub const JVMTI_HEAP_REFERENCE_SIGNERS: jvmtiHeapReferenceKind = 5;
+pub const JVMTI_HEAP_REFERENCE_PROTECTION_DOMAIN: jvmtiHeapReferenceKind = 6;
+pub const JVMTI_HEAP_REFERENCE_INTERFACE: jvmtiHeapReferenceKind = 7;
+pub const JVMTI_HEAP_REFERENCE_STATIC_FIELD: jvmtiHeapReferenceKind = 8;
+pub const JVMTI_HEAP_REFERENCE_CONSTANT_POOL: jvmtiHeapReferenceKind = 9;
+pub const JVMTI_HEAP_REFERENCE_SUPERCLASS: jvmtiHeapReferenceKind = 10;
+pub const JVMTI_HEAP_REFERENCE_JNI_GLOBAL: jvmtiHeapReferenceKind = 21;
+pub const JVMTI_HEAP_REFERENCE_SYSTEM_CLASS: jvmtiHeapReferenceKind = 22;
+pub const JVMTI_HEAP_REFERENCE_MONITOR: jvmtiHeapReferenceKind = 23;
+pub const JVMTI_HEAP_REFERENCE_STACK_LOCAL: jvmtiHeapReferenceKind = 24;
+pub const JVMTI_HEAP_REFERENCE_JNI_LOCAL: jvmtiHeapReferenceKind = 25;
+pub const JVMTI_HEAP_REFERENCE_THREAD: jvmtiHeapReferenceKind = 26;
+pub const JVMTI_HEAP_REFERENCE_OTHER: jvmtiHeapReferenceKind = 27;
+
+// Primitive Type Enumeration
+
+pub type jvmtiPrimitiveType = jint;
+
+pub const JVMTI_PRIMITIVE_TYPE_BOOLEAN: jvmtiPrimitiveType = 90;
+pub const JVMTI_PRIMITIVE_TYPE_BYTE: jvmtiPrimitiveType = 66;
+pub const JVMTI_PRIMITIVE_TYPE_CHAR: jvmtiPrimitiveType = 67;
+pub const JVMTI_PRIMITIVE_TYPE_SHORT: jvmtiPrimitiveType = 83;
+pub const JVMTI_PRIMITIVE_TYPE_INT: jvmtiPrimitiveType = 73;
+pub const JVMTI_PRIMITIVE_TYPE_LONG: jvmtiPrimitiveType = 74;
+pub const JVMTI_PRIMITIVE_TYPE_FLOAT: jvmtiPrimitiveType = 70;
+pub const JVMTI_PRIMITIVE_TYPE_DOUBLE: jvmtiPrimitiveType = 68;
+
+// Heap Object Filter Enumeration
+
+pub type jvmtiHeapObjectFilter = jint;
+
+pub const JVMTI_HEAP_OBJECT_TAGGED: jvmtiHeapObjectFilter = 1;
+pub const JVMTI_HEAP_OBJECT_UNTAGGED: jvmtiHeapObjectFilter = 2;
+pub const JVMTI_HEAP_OBJECT_EITHER: jvmtiHeapObjectFilter = 3;
+
+// Heap Root Kind Enumeration
+
+pub type jvmtiHeapRootKind = jint;
+
+pub const JVMTI_HEAP_ROOT_JNI_GLOBAL: jvmtiHeapRootKind = 1;
+pub const JVMTI_HEAP_ROOT_SYSTEM_CLASS: jvmtiHeapRootKind = 2;
+pub const JVMTI_HEAP_ROOT_MONITOR: jvmtiHeapRootKind = 3;
+pub const JVMTI_HEAP_ROOT_STACK_LOCAL: jvmtiHeapRootKind = 4;
+pub const JVMTI_HEAP_ROOT_JNI_LOCAL: jvmtiHeapRootKind = 5;
+pub const JVMTI_HEAP_ROOT_THREAD: jvmtiHeapRootKind = 6;
+pub const JVMTI_HEAP_ROOT_OTHER: jvmtiHeapRootKind = 7;
+
+// Object Reference Enumeration
+
+pub type jvmtiObjectReferenceKind = jint;
+
+pub const JVMTI_REFERENCE_CLASS: jvmtiObjectReferenceKind = 1;
+pub const JVMTI_REFERENCE_FIELD: jvmtiObjectReferenceKind = 2;
+pub const JVMTI_REFERENCE_ARRAY_ELEMENT: jvmtiObjectReferenceKind = 3;
+pub const JVMTI_REFERENCE_CLASS_LOADER: jvmtiObjectReferenceKind = 4;
+pub const JVMTI_REFERENCE_SIGNERS: jvmtiObjectReferenceKind = 5;
+pub const JVMTI_REFERENCE_PROTECTION_DOMAIN: jvmtiObjectReferenceKind = 6;
+pub const JVMTI_REFERENCE_INTERFACE: jvmtiObjectReferenceKind = 7;
+pub const JVMTI_REFERENCE_STATIC_FIELD: jvmtiObjectReferenceKind = 8;
+pub const JVMTI_REFERENCE_CONSTANT_POOL: jvmtiObjectReferenceKind = 9;
+
+// Iteration Control Enumeration
+
+pub type jvmtiIterationControl = jint;
+
+pub const JVMTI_ITERATION_CONTINUE: jvmtiIterationControl = 1;
+pub const JVMTI_ITERATION_IGNORE: jvmtiIterationControl = 2;
+pub const JVMTI_ITERATION_ABORT: jvmtiIterationControl = 0;
+
+// Class Status Flags
+
+pub const JVMTI_CLASS_STATUS_VERIFIED: jint = 1;
+pub const JVMTI_CLASS_STATUS_PREPARED: jint = 2;
+pub const JVMTI_CLASS_STATUS_INITIALIZED: jint = 4;
+pub const JVMTI_CLASS_STATUS_ERROR: jint = 8;
+pub const JVMTI_CLASS_STATUS_ARRAY: jint = 16;
+pub const JVMTI_CLASS_STATUS_PRIMITIVE: jint = 32;
+
+// Event Enable/Disable
+
+pub type jvmtiEventMode = jint;
+
+pub const JVMTI_ENABLE: jvmtiEventMode = 1;
+pub const JVMTI_DISABLE: jvmtiEventMode = 0;
+
+// Extension Function/Event Parameter Types
+
+pub type jvmtiParamTypes = jint;
+
+pub const JVMTI_TYPE_JBYTE: jvmtiParamTypes = 101;
+pub const JVMTI_TYPE_JCHAR: jvmtiParamTypes = 102;
+pub const JVMTI_TYPE_JSHORT: jvmtiParamTypes = 103;
+pub const JVMTI_TYPE_JINT: jvmtiParamTypes = 104;
+pub const JVMTI_TYPE_JLONG: jvmtiParamTypes = 105;
+pub const JVMTI_TYPE_JFLOAT: jvmtiParamTypes = 106;
+pub const JVMTI_TYPE_JDOUBLE: jvmtiParamTypes = 107;
+pub const JVMTI_TYPE_JBOOLEAN: jvmtiParamTypes = 108;
+pub const JVMTI_TYPE_JOBJECT: jvmtiParamTypes = 109;
+pub const JVMTI_TYPE_JTHREAD: jvmtiParamTypes = 110;
+pub const JVMTI_TYPE_JCLASS: jvmtiParamTypes = 111;
+pub const JVMTI_TYPE_JVALUE: jvmtiParamTypes = 112;
+pub const JVMTI_TYPE_JFIELDID: jvmtiParamTypes = 113;
+pub const JVMTI_TYPE_JMETHODID: jvmtiParamTypes = 114;
+pub const JVMTI_TYPE_CCHAR: jvmtiParamTypes = 115;
+pub const JVMTI_TYPE_CVOID: jvmtiParamTypes = 116;
+pub const JVMTI_TYPE_JNIENV: jvmtiParamTypes = 117;
+
+// Extension Function/Event Parameter Kinds
+
+pub type jvmtiParamKind = jint;
+
+pub const JVMTI_KIND_IN: jvmtiParamKind = 91;
+pub const JVMTI_KIND_IN_PTR: jvmtiParamKind = 92;
+pub const JVMTI_KIND_IN_BUF: jvmtiParamKind = 93;
+pub const JVMTI_KIND_ALLOC_BUF: jvmtiParamKind = 94;
+pub const JVMTI_KIND_ALLOC_ALLOC_BUF: jvmtiParamKind = 95;
+pub const JVMTI_KIND_OUT: jvmtiParamKind = 96;
+pub const JVMTI_KIND_OUT_BUF: jvmtiParamKind = 97;
+
+// Timer Kinds
+
+pub type jvmtiTimerKind = jint;
+
+pub const JVMTI_TIMER_USER_CPU: jvmtiTimerKind = 30;
+pub const JVMTI_TIMER_TOTAL_CPU: jvmtiTimerKind = 31;
+pub const JVMTI_TIMER_ELAPSED: jvmtiTimerKind = 32;
+
+// Phases of execution
+
+pub type jvmtiPhase = jint;
+
+pub const JVMTI_PHASE_ONLOAD: jvmtiPhase = 1;
+pub const JVMTI_PHASE_PRIMORDIAL: jvmtiPhase = 2;
+pub const JVMTI_PHASE_START: jvmtiPhase = 6;
+pub const JVMTI_PHASE_LIVE: jvmtiPhase = 4;
+pub const JVMTI_PHASE_DEAD: jvmtiPhase = 8;
+
+// Version Interface Types
+
+pub const JVMTI_VERSION_INTERFACE_JNI: jint = 0x00000000;
+pub const JVMTI_VERSION_INTERFACE_JVMTI: jint = 0x30000000;
+
+// Version Masks
+
+pub const JVMTI_VERSION_MASK_INTERFACE_TYPE: jint = 0x70000000;
+pub const JVMTI_VERSION_MASK_MAJOR: jint = 0x0FFF0000;
+pub const JVMTI_VERSION_MASK_MINOR: jint = 0x0000FF00;
+pub const JVMTI_VERSION_MASK_MICRO: jint = 0x000000FF;
+
+// Version Shifts
+
+pub const JVMTI_VERSION_SHIFT_MAJOR: jint = 16;
+pub const JVMTI_VERSION_SHIFT_MINOR: jint = 8;
+pub const JVMTI_VERSION_SHIFT_MICRO: jint = 0;
+
+// Verbose Flag Enumeration
+
+pub type jvmtiVerboseFlag = jint;
+
+pub const JVMTI_VERBOSE_OTHER: jvmtiVerboseFlag = 0;
+pub const JVMTI_VERBOSE_GC: jvmtiVerboseFlag = 1;
+pub const JVMTI_VERBOSE_CLASS: jvmtiVerboseFlag = 2;
+pub const JVMTI_VERBOSE_JNI: jvmtiVerboseFlag = 4;
+
+// JLocation Format Enumeration
+
+pub type jvmtiJlocationFormat = jint;
+
+pub const JVMTI_JLOCATION_JVMBCI: jvmtiJlocationFormat = 1;
+pub const JVMTI_JLOCATION_MACHINEPC: jvmtiJlocationFormat = 2;
+pub const JVMTI_JLOCATION_OTHER: jvmtiJlocationFormat = 0;
+
+// Resource Exhaustion Flags
+
+pub const JVMTI_RESOURCE_EXHAUSTED_OOM_ERROR: jint = 0x0001;
+pub const JVMTI_RESOURCE_EXHAUSTED_JAVA_HEAP: jint = 0x0002;
+pub const JVMTI_RESOURCE_EXHAUSTED_THREADS: jint = 0x0004;
+
+// Errors
+
+// Converted to and from `jint`, named and checked by the methods in `errors`
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct jvmtiError(jint);
+
+pub const JVMTI_ERROR_NONE: jvmtiError = jvmtiError(0);
+pub const JVMTI_ERROR_INVALID_THREAD: jvmtiError = jvmtiError(10);
+pub const JVMTI_ERROR_INVALID_THREAD_GROUP: jvmtiError = jvmtiError(11);
+pub const JVMTI_ERROR_INVALID_PRIORITY: jvmtiError = jvmtiError(12);
+pub const JVMTI_ERROR_THREAD_NOT_SUSPENDED: jvmtiError = jvmtiError(13);
+pub const JVMTI_ERROR_THREAD_SUSPENDED: jvmtiError = jvmtiError(14);
+pub const JVMTI_ERROR_THREAD_NOT_ALIVE: jvmtiError = jvmtiError(15);
+pub const JVMTI_ERROR_INVALID_OBJECT: jvmtiError = jvmtiError(20);
+pub const JVMTI_ERROR_INVALID_CLASS: jvmtiError = jvmtiError(21);
+pub const JVMTI_ERROR_CLASS_NOT_PREPARED: jvmtiError = jvmtiError(22);
+pub const JVMTI_ERROR_INVALID_METHODID: jvmtiError = jvmtiError(23);
+pub const JVMTI_ERROR_INVALID_LOCATION: jvmtiError = jvmtiError(24);
+pub const JVMTI_ERROR_INVALID_FIELDID: jvmtiError = jvmtiError(25);
+pub const JVMTI_ERROR_INVALID_MODULE: jvmtiError = jvmtiError(26);
+pub const JVMTI_ERROR_NO_MORE_FRAMES: jvmtiError = jvmtiError(31);
+pub const JVMTI_ERROR_OPAQUE_FRAME: jvmtiError = jvmtiError(32);
+pub const JVMTI_ERROR_TYPE_MISMATCH: jvmtiError = jvmtiError(34);
+pub const JVMTI_ERROR_INVALID_SLOT: jvmtiError = jvmtiError(35);
+pub const JVMTI_ERROR_DUPLICATE: jvmtiError = jvmtiError(40);
+pub const JVMTI_ERROR_NOT_FOUND: jvmtiError = jvmtiError(41);
+pub const JVMTI_ERROR_INVALID_MONITOR: jvmtiError = jvmtiError(50);
+pub const JVMTI_ERROR_NOT_MONITOR_OWNER: jvmtiError = jvmtiError(51);
+pub const JVMTI_ERROR_INTERRUPT: jvmtiError = jvmtiError(52);
+pub const JVMTI_ERROR_INVALID_CLASS_FORMAT: jvmtiError = jvmtiError(60);
+pub const JVMTI_ERROR_CIRCULAR_CLASS_DEFINITION: jvmtiError = jvmtiError(61);
+pub const JVMTI_ERROR_FAILS_VERIFICATION: jvmtiError = jvmtiError(62);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_METHOD_ADDED: jvmtiError = jvmtiError(63);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_SCHEMA_CHANGED: jvmtiError = jvmtiError(64);
+pub const JVMTI_ERROR_INVALID_TYPESTATE: jvmtiError = jvmtiError(65);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_HIERARCHY_CHANGED: jvmtiError = jvmtiError(66);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_METHOD_DELETED: jvmtiError = jvmtiError(67);
+pub const JVMTI_ERROR_UNSUPPORTED_VERSION: jvmtiError = jvmtiError(68);
+pub const JVMTI_ERROR_NAMES_DONT_MATCH: jvmtiError = jvmtiError(69);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_CLASS_MODIFIERS_CHANGED: jvmtiError = jvmtiError(70);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_METHOD_MODIFIERS_CHANGED: jvmtiError =
+    jvmtiError(71);
+pub const JVMTI_ERROR_UNSUPPORTED_REDEFINITION_CLASS_ATTRIBUTE_CHANGED: jvmtiError = jvmtiError(72);
+pub const JVMTI_ERROR_UNSUPPORTED_OPERATION: jvmtiError = jvmtiError(73);
+pub const JVMTI_ERROR_UNMODIFIABLE_CLASS: jvmtiError = jvmtiError(79);
+pub const JVMTI_ERROR_UNMODIFIABLE_MODULE: jvmtiError = jvmtiError(80);
+pub const JVMTI_ERROR_NOT_AVAILABLE: jvmtiError = jvmtiError(98);
+pub const JVMTI_ERROR_MUST_POSSESS_CAPABILITY: jvmtiError = jvmtiError(99);
+pub const JVMTI_ERROR_NULL_POINTER: jvmtiError = jvmtiError(100);
+pub const JVMTI_ERROR_ABSENT_INFORMATION: jvmtiError = jvmtiError(101);
+pub const JVMTI_ERROR_INVALID_EVENT_TYPE: jvmtiError = jvmtiError(102);
+pub const JVMTI_ERROR_ILLEGAL_ARGUMENT: jvmtiError = jvmtiError(103);
+pub const JVMTI_ERROR_NATIVE_METHOD: jvmtiError = jvmtiError(104);
+pub const JVMTI_ERROR_CLASS_LOADER_UNSUPPORTED: jvmtiError = jvmtiError(106);
+pub const JVMTI_ERROR_OUT_OF_MEMORY: jvmtiError = jvmtiError(110);
+pub const JVMTI_ERROR_ACCESS_DENIED: jvmtiError = jvmtiError(111);
+pub const JVMTI_ERROR_WRONG_PHASE: jvmtiError = jvmtiError(112);
+pub const JVMTI_ERROR_INTERNAL: jvmtiError = jvmtiError(113);
+pub const JVMTI_ERROR_UNATTACHED_THREAD: jvmtiError = jvmtiError(115);
+pub const JVMTI_ERROR_INVALID_ENVIRONMENT: jvmtiError = jvmtiError(116);
+pub const JVMTI_ERROR_MAX: jvmtiError = jvmtiError(116);
+
+// Event IDs
+
+// Converted to and from `jint` and named by the methods in `events`
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct jvmtiEvent(jint);
+
+pub const JVMTI_MIN_EVENT_TYPE_VAL: jvmtiEvent = jvmtiEvent(50);
+pub const JVMTI_EVENT_VM_INIT: jvmtiEvent = jvmtiEvent(50);
+pub const JVMTI_EVENT_VM_DEATH: jvmtiEvent = jvmtiEvent(51);
+pub const JVMTI_EVENT_THREAD_START: jvmtiEvent = jvmtiEvent(52);
+pub const JVMTI_EVENT_THREAD_END: jvmtiEvent = jvmtiEvent(53);
+pub const JVMTI_EVENT_CLASS_FILE_LOAD_HOOK: jvmtiEvent = jvmtiEvent(54);
+pub const JVMTI_EVENT_CLASS_LOAD: jvmtiEvent = jvmtiEvent(55);
+pub const JVMTI_EVENT_CLASS_PREPARE: jvmtiEvent = jvmtiEvent(56);
+pub const JVMTI_EVENT_VM_START: jvmtiEvent = jvmtiEvent(57);
+pub const JVMTI_EVENT_EXCEPTION: jvmtiEvent = jvmtiEvent(58);
+pub const JVMTI_EVENT_EXCEPTION_CATCH: jvmtiEvent = jvmtiEvent(59);
+pub const JVMTI_EVENT_SINGLE_STEP: jvmtiEvent = jvmtiEvent(60);
+pub const JVMTI_EVENT_FRAME_POP: jvmtiEvent = jvmtiEvent(61);
+pub const JVMTI_EVENT_BREAKPOINT: jvmtiEvent = jvmtiEvent(62);
+pub const JVMTI_EVENT_FIELD_ACCESS: jvmtiEvent = jvmtiEvent(63);
+pub const JVMTI_EVENT_FIELD_MODIFICATION: jvmtiEvent = jvmtiEvent(64);
+pub const JVMTI_EVENT_METHOD_ENTRY: jvmtiEvent = jvmtiEvent(65);
+pub const JVMTI_EVENT_METHOD_EXIT: jvmtiEvent = jvmtiEvent(66);
+pub const JVMTI_EVENT_NATIVE_METHOD_BIND: jvmtiEvent = jvmtiEvent(67);
+pub const JVMTI_EVENT_COMPILED_METHOD_LOAD: jvmtiEvent = jvmtiEvent(68);
+pub const JVMTI_EVENT_COMPILED_METHOD_UNLOAD: jvmtiEvent = jvmtiEvent(69);
+pub const JVMTI_EVENT_DYNAMIC_CODE_GENERATED: jvmtiEvent = jvmtiEvent(70);
+pub const JVMTI_EVENT_DATA_DUMP_REQUEST: jvmtiEvent = jvmtiEvent(71);
+pub const JVMTI_EVENT_MONITOR_WAIT: jvmtiEvent = jvmtiEvent(73);
+pub const JVMTI_EVENT_MONITOR_WAITED: jvmtiEvent = jvmtiEvent(74);
+pub const JVMTI_EVENT_MONITOR_CONTENDED_ENTER: jvmtiEvent = jvmtiEvent(75);
+pub const JVMTI_EVENT_MONITOR_CONTENDED_ENTERED: jvmtiEvent = jvmtiEvent(76);
+pub const JVMTI_EVENT_RESOURCE_EXHAUSTED: jvmtiEvent = jvmtiEvent(80);
+pub const JVMTI_EVENT_GARBAGE_COLLECTION_START: jvmtiEvent = jvmtiEvent(81);
+pub const JVMTI_EVENT_GARBAGE_COLLECTION_FINISH: jvmtiEvent = jvmtiEvent(82);
+pub const JVMTI_EVENT_OBJECT_FREE: jvmtiEvent = jvmtiEvent(83);
+pub const JVMTI_EVENT_VM_OBJECT_ALLOC: jvmtiEvent = jvmtiEvent(84);
+pub const JVMTI_EVENT_SAMPLED_OBJECT_ALLOC: jvmtiEvent = jvmtiEvent(86);
+pub const JVMTI_EVENT_VIRTUAL_THREAD_START: jvmtiEvent = jvmtiEvent(87);
+pub const JVMTI_EVENT_VIRTUAL_THREAD_END: jvmtiEvent = jvmtiEvent(88);
+pub const JVMTI_MAX_EVENT_TYPE_VAL: jvmtiEvent = jvmtiEvent(88);
+
+pub type jvmtiStartFunction = Option<
+    unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, jni_env: *mut JNIEnv, arg: *mut c_void),
+>;
+pub type jvmtiHeapIterationCallback = Option<
+    unsafe extern "system" fn(
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        length: jint,
+        user_data: *mut c_void,
+    ) -> jint,
+>;
+pub type jvmtiHeapReferenceCallback = Option<
+    unsafe extern "system" fn(
+        reference_kind: jvmtiHeapReferenceKind,
+        reference_info: *const jvmtiHeapReferenceInfo,
+        class_tag: jlong,
+        referrer_class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        referrer_tag_ptr: *mut jlong,
+        length: jint,
+        user_data: *mut c_void,
+    ) -> jint,
+>;
+pub type jvmtiPrimitiveFieldCallback = Option<
+    unsafe extern "system" fn(
+        kind: jvmtiHeapReferenceKind,
+        info: *const jvmtiHeapReferenceInfo,
+        object_class_tag: jlong,
+        object_tag_ptr: *mut jlong,
+        value: jvalue,
+        value_type: jvmtiPrimitiveType,
+        user_data: *mut c_void,
+    ) -> jint,
+>;
+pub type jvmtiArrayPrimitiveValueCallback = Option<
+    unsafe extern "system" fn(
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        element_count: jint,
+        element_type: jvmtiPrimitiveType,
+        elements: *const c_void,
+        user_data: *mut c_void,
+    ) -> jint,
+>;
+pub type jvmtiStringPrimitiveValueCallback = Option<
+    unsafe extern "system" fn(
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        value: *const jchar,
+        value_length: jint,
+        user_data: *mut c_void,
+    ) -> jint,
+>;
+pub type jvmtiReservedCallback = Option<unsafe extern "system" fn() -> jint>;
+pub type jvmtiHeapObjectCallback = Option<
+    unsafe extern "system" fn(
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        user_data: *mut c_void,
+    ) -> jvmtiIterationControl,
+>;
+pub type jvmtiHeapRootCallback = Option<
+    unsafe extern "system" fn(
+        root_kind: jvmtiHeapRootKind,
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        user_data: *mut c_void,
+    ) -> jvmtiIterationControl,
+>;
+pub type jvmtiStackReferenceCallback = Option<
+    unsafe extern "system" fn(
+        root_kind: jvmtiHeapRootKind,
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        thread_tag: jlong,
+        depth: jint,
+        method: jmethodID,
+        slot: jint,
+        user_data: *mut c_void,
+    ) -> jvmtiIterationControl,
+>;
+pub type jvmtiObjectReferenceCallback = Option<
+    unsafe extern "system" fn(
+        reference_kind: jvmtiObjectReferenceKind,
+        class_tag: jlong,
+        size: jlong,
+        tag_ptr: *mut jlong,
+        referrer_tag: jlong,
+        referrer_index: jint,
+        user_data: *mut c_void,
+    ) -> jvmtiIterationControl,
+>;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiThreadInfo {
+    pub name: *mut c_char,
+    pub priority: jint,
+    pub is_daemon: jboolean,
+    pub thread_group: jthreadGroup,
+    pub context_class_loader: jobject,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiMonitorStackDepthInfo {
+    pub monitor: jobject,
+    pub stack_depth: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiThreadGroupInfo {
+    pub parent: jthreadGroup,
+    pub name: *mut c_char,
+    pub max_priority: jint,
+    pub is_daemon: jboolean,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiFrameInfo {
+    pub method: jmethodID,
+    pub location: jlocation,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiStackInfo {
+    pub thread: jthread,
+    pub state: jint,
+    pub frame_buffer: *mut jvmtiFrameInfo,
+    pub frame_count: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapReferenceInfoField {
+    pub index: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapReferenceInfoArray {
+    pub index: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapReferenceInfoConstantPool {
+    pub index: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapReferenceInfoStackLocal {
+    pub thread_tag: jlong,
+    pub thread_id: jlong,
+    pub depth: jint,
+    pub method: jmethodID,
+    pub location: jlocation,
+    pub slot: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapReferenceInfoJniLocal {
+    pub thread_tag: jlong,
+    pub thread_id: jlong,
+    pub depth: jint,
+    pub method: jmethodID,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapReferenceInfoReserved {
+    pub reserved1: jlong,
+    pub reserved2: jlong,
+    pub reserved3: jlong,
+    pub reserved4: jlong,
+    pub reserved5: jlong,
+    pub reserved6: jlong,
+    pub reserved7: jlong,
+    pub reserved8: jlong,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union jvmtiHeapReferenceInfo {
+    pub field: jvmtiHeapReferenceInfoField,
+    pub array: jvmtiHeapReferenceInfoArray,
+    pub constant_pool: jvmtiHeapReferenceInfoConstantPool,
+    pub stack_local: jvmtiHeapReferenceInfoStackLocal,
+    pub jni_local: jvmtiHeapReferenceInfoJniLocal,
+    pub other: jvmtiHeapReferenceInfoReserved,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiHeapCallbacks {
+    pub heap_iteration_callback: jvmtiHeapIterationCallback,
+    pub heap_reference_callback: jvmtiHeapReferenceCallback,
+    pub primitive_field_callback: jvmtiPrimitiveFieldCallback,
+    pub array_primitive_value_callback: jvmtiArrayPrimitiveValueCallback,
+    pub string_primitive_value_callback: jvmtiStringPrimitiveValueCallback,
+    pub reserved5: jvmtiReservedCallback,
+    pub reserved6: jvmtiReservedCallback,
+    pub reserved7: jvmtiReservedCallback,
+    pub reserved8: jvmtiReservedCallback,
+    pub reserved9: jvmtiReservedCallback,
+    pub reserved10: jvmtiReservedCallback,
+    pub reserved11: jvmtiReservedCallback,
+    pub reserved12: jvmtiReservedCallback,
+    pub reserved13: jvmtiReservedCallback,
+    pub reserved14: jvmtiReservedCallback,
+    pub reserved15: jvmtiReservedCallback,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiClassDefinition {
+    pub klass: jclass,
+    pub class_byte_count: jint,
+    pub class_bytes: *const c_uchar,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiMonitorUsage {
+    pub owner: jthread,
+    pub entry_count: jint,
+    pub waiter_count: jint,
+    pub waiters: *mut jthread,
+    pub notify_waiter_count: jint,
+    pub notify_waiters: *mut jthread,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiLineNumberEntry {
+    pub start_location: jlocation,
+    pub line_number: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiLocalVariableEntry {
+    pub start_location: jlocation,
+    pub length: jint,
+    pub name: *mut c_char,
+    pub signature: *mut c_char,
+    pub generic_signature: *mut c_char,
+    pub slot: jint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiParamInfo {
+    pub name: *mut c_char,
+    pub kind: jvmtiParamKind,
+    pub base_type: jvmtiParamTypes,
+    pub null_ok: jboolean,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiExtensionFunctionInfo {
+    pub func: jvmtiExtensionFunction,
+    pub id: *mut c_char,
+    pub short_description: *mut c_char,
+    pub param_count: jint,
+    pub params: *mut jvmtiParamInfo,
+    pub error_count: jint,
+    pub errors: *mut jvmtiError,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiExtensionEventInfo {
+    pub extension_event_index: jint,
+    pub id: *mut c_char,
+    pub short_description: *mut c_char,
+    pub param_count: jint,
+    pub params: *mut jvmtiParamInfo,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiTimerInfo {
+    pub max_value: jlong,
+    pub may_skip_forward: jboolean,
+    pub may_skip_backward: jboolean,
+    pub kind: jvmtiTimerKind,
+    pub reserved1: jlong,
+    pub reserved2: jlong,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct jvmtiAddrLocationMap {
+    pub start_address: *const c_void,
+    pub location: jlocation,
+}
+
+impl core::fmt::Debug for jvmtiHeapReferenceInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // The active member depends on the reference kind passed with the union
+        f.debug_struct("jvmtiHeapReferenceInfo")
+            .finish_non_exhaustive()
+    }
+}
+
+pub type jvmtiExtensionFunction =
+    Option<unsafe extern "C" fn(jvmti_env: *mut jvmtiEnv, ...) -> jvmtiError>;
+pub type jvmtiExtensionEvent = Option<unsafe extern "C" fn(jvmti_env: *mut jvmtiEnv, ...)>;
+
+// The bitfields are read and written through the methods in `capabilities`
+#[repr(C)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct jvmtiCapabilities {
+    bits: [c_uint; 4],
+}
+
+pub type jvmtiEventReserved = Option<unsafe extern "system" fn()>;
+pub type jvmtiEventBreakpoint = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        location: jlocation,
+    ),
+>;
+pub type jvmtiEventClassFileLoadHook = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        class_being_redefined: jclass,
+        loader: jobject,
+        name: *const c_char,
+        protection_domain: jobject,
+        class_data_len: jint,
+        class_data: *const c_uchar,
+        new_class_data_len: *mut jint,
+        new_class_data: *mut *mut c_uchar,
+    ),
+>;
+pub type jvmtiEventClassLoad = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        klass: jclass,
+    ),
+>;
+pub type jvmtiEventClassPrepare = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        klass: jclass,
+    ),
+>;
+pub type jvmtiEventCompiledMethodLoad = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        method: jmethodID,
+        code_size: jint,
+        code_addr: *const c_void,
+        map_length: jint,
+        map: *const jvmtiAddrLocationMap,
+        compile_info: *const c_void,
+    ),
+>;
+pub type jvmtiEventCompiledMethodUnload = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        method: jmethodID,
+        code_addr: *const c_void,
+    ),
+>;
+pub type jvmtiEventDataDumpRequest = Option<unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv)>;
+pub type jvmtiEventDynamicCodeGenerated = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        name: *const c_char,
+        address: *const c_void,
+        length: jint,
+    ),
+>;
+pub type jvmtiEventException = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        location: jlocation,
+        exception: jobject,
+        catch_method: jmethodID,
+        catch_location: jlocation,
+    ),
+>;
+pub type jvmtiEventExceptionCatch = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        location: jlocation,
+        exception: jobject,
+    ),
+>;
+pub type jvmtiEventFieldAccess = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        location: jlocation,
+        field_klass: jclass,
+        object: jobject,
+        field: jfieldID,
+    ),
+>;
+pub type jvmtiEventFieldModification = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        location: jlocation,
+        field_klass: jclass,
+        object: jobject,
+        field: jfieldID,
+        signature_type: c_char,
+        new_value: jvalue,
+    ),
+>;
+pub type jvmtiEventFramePop = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        was_popped_by_exception: jboolean,
+    ),
+>;
+pub type jvmtiEventGarbageCollectionFinish =
+    Option<unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv)>;
+pub type jvmtiEventGarbageCollectionStart =
+    Option<unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv)>;
+pub type jvmtiEventMethodEntry = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+    ),
+>;
+pub type jvmtiEventMethodExit = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        was_popped_by_exception: jboolean,
+        return_value: jvalue,
+    ),
+>;
+pub type jvmtiEventMonitorContendedEnter = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        object: jobject,
+    ),
+>;
+pub type jvmtiEventMonitorContendedEntered = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        object: jobject,
+    ),
+>;
+pub type jvmtiEventMonitorWait = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        object: jobject,
+        timeout: jlong,
+    ),
+>;
+pub type jvmtiEventMonitorWaited = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        object: jobject,
+        timed_out: jboolean,
+    ),
+>;
+pub type jvmtiEventNativeMethodBind = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        address: *mut c_void,
+        new_address_ptr: *mut *mut c_void,
+    ),
+>;
+pub type jvmtiEventObjectFree =
+    Option<unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, tag: jlong)>;
+pub type jvmtiEventResourceExhausted = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        flags: jint,
+        reserved: *const c_void,
+        description: *const c_char,
+    ),
+>;
+pub type jvmtiEventSampledObjectAlloc = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        object: jobject,
+        object_klass: jclass,
+        size: jlong,
+    ),
+>;
+pub type jvmtiEventSingleStep = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        method: jmethodID,
+        location: jlocation,
+    ),
+>;
+pub type jvmtiEventThreadEnd = Option<
+    unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, jni_env: *mut JNIEnv, thread: jthread),
+>;
+pub type jvmtiEventThreadStart = Option<
+    unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, jni_env: *mut JNIEnv, thread: jthread),
+>;
+pub type jvmtiEventVMDeath =
+    Option<unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, jni_env: *mut JNIEnv)>;
+pub type jvmtiEventVMInit = Option<
+    unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, jni_env: *mut JNIEnv, thread: jthread),
+>;
+pub type jvmtiEventVMObjectAlloc = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        thread: jthread,
+        object: jobject,
+        object_klass: jclass,
+        size: jlong,
+    ),
+>;
+pub type jvmtiEventVMStart =
+    Option<unsafe extern "system" fn(jvmti_env: *mut jvmtiEnv, jni_env: *mut JNIEnv)>;
+pub type jvmtiEventVirtualThreadEnd = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        virtual_thread: jthread,
+    ),
+>;
+pub type jvmtiEventVirtualThreadStart = Option<
+    unsafe extern "system" fn(
+        jvmti_env: *mut jvmtiEnv,
+        jni_env: *mut JNIEnv,
+        virtual_thread: jthread,
+    ),
+>;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct jvmtiEventCallbacks {
+    pub VMInit: jvmtiEventVMInit,
+    pub VMDeath: jvmtiEventVMDeath,
+    pub ThreadStart: jvmtiEventThreadStart,
+    pub ThreadEnd: jvmtiEventThreadEnd,
+    pub ClassFileLoadHook: jvmtiEventClassFileLoadHook,
+    pub ClassLoad: jvmtiEventClassLoad,
+    pub ClassPrepare: jvmtiEventClassPrepare,
+    pub VMStart: jvmtiEventVMStart,
+    pub Exception: jvmtiEventException,
+    pub ExceptionCatch: jvmtiEventExceptionCatch,
+    pub SingleStep: jvmtiEventSingleStep,
+    pub FramePop: jvmtiEventFramePop,
+    pub Breakpoint: jvmtiEventBreakpoint,
+    pub FieldAccess: jvmtiEventFieldAccess,
+    pub FieldModification: jvmtiEventFieldModification,
+    pub MethodEntry: jvmtiEventMethodEntry,
+    pub MethodExit: jvmtiEventMethodExit,
+    pub NativeMethodBind: jvmtiEventNativeMethodBind,
+    pub CompiledMethodLoad: jvmtiEventCompiledMethodLoad,
+    pub CompiledMethodUnload: jvmtiEventCompiledMethodUnload,
+    pub DynamicCodeGenerated: jvmtiEventDynamicCodeGenerated,
+    pub DataDumpRequest: jvmtiEventDataDumpRequest,
+    pub reserved72: jvmtiEventReserved,
+    pub MonitorWait: jvmtiEventMonitorWait,
+    pub MonitorWaited: jvmtiEventMonitorWaited,
+    pub MonitorContendedEnter: jvmtiEventMonitorContendedEnter,
+    pub MonitorContendedEntered: jvmtiEventMonitorContendedEntered,
+    pub reserved77: jvmtiEventReserved,
+    pub reserved78: jvmtiEventReserved,
+    pub reserved79: jvmtiEventReserved,
+    pub ResourceExhausted: jvmtiEventResourceExhausted,
+    pub GarbageCollectionStart: jvmtiEventGarbageCollectionStart,
+    pub GarbageCollectionFinish: jvmtiEventGarbageCollectionFinish,
+    pub ObjectFree: jvmtiEventObjectFree,
+    pub VMObjectAlloc: jvmtiEventVMObjectAlloc,
+    pub reserved85: jvmtiEventReserved,
+    pub SampledObjectAlloc: jvmtiEventSampledObjectAlloc,
+    pub VirtualThreadStart: jvmtiEventVirtualThreadStart,
+    pub VirtualThreadEnd: jvmtiEventVirtualThreadEnd,
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[jni_to_union(version_prefix = "JVMTI_VERSION", default_version = "1.0")]
+#[derive(Copy, Clone)]
+pub struct jvmtiInterface_1_ {
+    #[jni_added("reserved")]
+    pub reserved1: *mut c_void,
+    pub SetEventNotificationMode: unsafe extern "C" fn(
+        env: *mut jvmtiEnv,
+        mode: jvmtiEventMode,
+        event_type: jvmtiEvent,
+        event_thread: jthread,
+        ...
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub GetAllModules: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module_count_ptr: *mut jint,
+        modules_ptr: *mut *mut jobject,
+    ) -> jvmtiError,
+    pub GetAllThreads: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        threads_count_ptr: *mut jint,
+        threads_ptr: *mut *mut jthread,
+    ) -> jvmtiError,
+    pub SuspendThread: unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread) -> jvmtiError,
+    pub ResumeThread: unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread) -> jvmtiError,
+    pub StopThread: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        exception: jobject,
+    ) -> jvmtiError,
+    pub InterruptThread:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread) -> jvmtiError,
+    pub GetThreadInfo: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        info_ptr: *mut jvmtiThreadInfo,
+    ) -> jvmtiError,
+    pub GetOwnedMonitorInfo: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        owned_monitor_count_ptr: *mut jint,
+        owned_monitors_ptr: *mut *mut jobject,
+    ) -> jvmtiError,
+    pub GetCurrentContendedMonitor: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        monitor_ptr: *mut jobject,
+    ) -> jvmtiError,
+    pub RunAgentThread: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        proc: jvmtiStartFunction,
+        arg: *const c_void,
+        priority: jint,
+    ) -> jvmtiError,
+    pub GetTopThreadGroups: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        group_count_ptr: *mut jint,
+        groups_ptr: *mut *mut jthreadGroup,
+    ) -> jvmtiError,
+    pub GetThreadGroupInfo: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        group: jthreadGroup,
+        info_ptr: *mut jvmtiThreadGroupInfo,
+    ) -> jvmtiError,
+    pub GetThreadGroupChildren: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        group: jthreadGroup,
+        thread_count_ptr: *mut jint,
+        threads_ptr: *mut *mut jthread,
+        group_count_ptr: *mut jint,
+        groups_ptr: *mut *mut jthreadGroup,
+    ) -> jvmtiError,
+    pub GetFrameCount: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        count_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetThreadState: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        thread_state_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetCurrentThread:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread_ptr: *mut jthread) -> jvmtiError,
+    pub GetFrameLocation: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        method_ptr: *mut jmethodID,
+        location_ptr: *mut jlocation,
+    ) -> jvmtiError,
+    pub NotifyFramePop:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread, depth: jint) -> jvmtiError,
+    pub GetLocalObject: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value_ptr: *mut jobject,
+    ) -> jvmtiError,
+    pub GetLocalInt: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetLocalLong: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value_ptr: *mut jlong,
+    ) -> jvmtiError,
+    pub GetLocalFloat: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value_ptr: *mut jfloat,
+    ) -> jvmtiError,
+    pub GetLocalDouble: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value_ptr: *mut jdouble,
+    ) -> jvmtiError,
+    pub SetLocalObject: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value: jobject,
+    ) -> jvmtiError,
+    pub SetLocalInt: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value: jint,
+    ) -> jvmtiError,
+    pub SetLocalLong: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value: jlong,
+    ) -> jvmtiError,
+    pub SetLocalFloat: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value: jfloat,
+    ) -> jvmtiError,
+    pub SetLocalDouble: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        slot: jint,
+        value: jdouble,
+    ) -> jvmtiError,
+    pub CreateRawMonitor: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        name: *const c_char,
+        monitor_ptr: *mut jrawMonitorID,
+    ) -> jvmtiError,
+    pub DestroyRawMonitor:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, monitor: jrawMonitorID) -> jvmtiError,
+    pub RawMonitorEnter:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, monitor: jrawMonitorID) -> jvmtiError,
+    pub RawMonitorExit:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, monitor: jrawMonitorID) -> jvmtiError,
+    pub RawMonitorWait: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        monitor: jrawMonitorID,
+        millis: jlong,
+    ) -> jvmtiError,
+    pub RawMonitorNotify:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, monitor: jrawMonitorID) -> jvmtiError,
+    pub RawMonitorNotifyAll:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, monitor: jrawMonitorID) -> jvmtiError,
+    pub SetBreakpoint: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        location: jlocation,
+    ) -> jvmtiError,
+    pub ClearBreakpoint: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        location: jlocation,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub GetNamedModule: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        class_loader: jobject,
+        package_name: *const c_char,
+        module_ptr: *mut jobject,
+    ) -> jvmtiError,
+    pub SetFieldAccessWatch:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, klass: jclass, field: jfieldID) -> jvmtiError,
+    pub ClearFieldAccessWatch:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, klass: jclass, field: jfieldID) -> jvmtiError,
+    pub SetFieldModificationWatch:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, klass: jclass, field: jfieldID) -> jvmtiError,
+    pub ClearFieldModificationWatch:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, klass: jclass, field: jfieldID) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub IsModifiableClass: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        is_modifiable_class_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub Allocate: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        size: jlong,
+        mem_ptr: *mut *mut c_uchar,
+    ) -> jvmtiError,
+    pub Deallocate: unsafe extern "system" fn(env: *mut jvmtiEnv, mem: *mut c_uchar) -> jvmtiError,
+    pub GetClassSignature: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        signature_ptr: *mut *mut c_char,
+        generic_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetClassStatus: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        status_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetSourceFileName: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        source_name_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetClassModifiers: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        modifiers_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetClassMethods: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        method_count_ptr: *mut jint,
+        methods_ptr: *mut *mut jmethodID,
+    ) -> jvmtiError,
+    pub GetClassFields: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        field_count_ptr: *mut jint,
+        fields_ptr: *mut *mut jfieldID,
+    ) -> jvmtiError,
+    pub GetImplementedInterfaces: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        interface_count_ptr: *mut jint,
+        interfaces_ptr: *mut *mut jclass,
+    ) -> jvmtiError,
+    pub IsInterface: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        is_interface_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub IsArrayClass: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        is_array_class_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub GetClassLoader: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        classloader_ptr: *mut jobject,
+    ) -> jvmtiError,
+    pub GetObjectHashCode: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        object: jobject,
+        hash_code_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetObjectMonitorUsage: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        object: jobject,
+        info_ptr: *mut jvmtiMonitorUsage,
+    ) -> jvmtiError,
+    pub GetFieldName: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        field: jfieldID,
+        name_ptr: *mut *mut c_char,
+        signature_ptr: *mut *mut c_char,
+        generic_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetFieldDeclaringClass: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        field: jfieldID,
+        declaring_class_ptr: *mut jclass,
+    ) -> jvmtiError,
+    pub GetFieldModifiers: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        field: jfieldID,
+        modifiers_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub IsFieldSynthetic: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        field: jfieldID,
+        is_synthetic_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub GetMethodName: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        name_ptr: *mut *mut c_char,
+        signature_ptr: *mut *mut c_char,
+        generic_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetMethodDeclaringClass: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        declaring_class_ptr: *mut jclass,
+    ) -> jvmtiError,
+    pub GetMethodModifiers: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        modifiers_ptr: *mut jint,
+    ) -> jvmtiError,
+    #[jni_added("reserved")]
+    pub reserved67: *mut c_void,
+    pub GetMaxLocals: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        max_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetArgumentsSize: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        size_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetLineNumberTable: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        entry_count_ptr: *mut jint,
+        table_ptr: *mut *mut jvmtiLineNumberEntry,
+    ) -> jvmtiError,
+    pub GetMethodLocation: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        start_location_ptr: *mut jlocation,
+        end_location_ptr: *mut jlocation,
+    ) -> jvmtiError,
+    pub GetLocalVariableTable: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        entry_count_ptr: *mut jint,
+        table_ptr: *mut *mut jvmtiLocalVariableEntry,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub SetNativeMethodPrefix:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, prefix: *const c_char) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub SetNativeMethodPrefixes: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        prefix_count: jint,
+        prefixes: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetBytecodes: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        bytecode_count_ptr: *mut jint,
+        bytecodes_ptr: *mut *mut c_uchar,
+    ) -> jvmtiError,
+    pub IsMethodNative: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        is_native_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub IsMethodSynthetic: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        is_synthetic_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub GetLoadedClasses: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        class_count_ptr: *mut jint,
+        classes_ptr: *mut *mut jclass,
+    ) -> jvmtiError,
+    pub GetClassLoaderClasses: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        initiating_loader: jobject,
+        class_count_ptr: *mut jint,
+        classes_ptr: *mut *mut jclass,
+    ) -> jvmtiError,
+    pub PopFrame: unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub ForceEarlyReturnObject: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        value: jobject,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub ForceEarlyReturnInt:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread, value: jint) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub ForceEarlyReturnLong:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread, value: jlong) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub ForceEarlyReturnFloat:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread, value: jfloat) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub ForceEarlyReturnDouble: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        value: jdouble,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub ForceEarlyReturnVoid:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, thread: jthread) -> jvmtiError,
+    pub RedefineClasses: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        class_count: jint,
+        class_definitions: *const jvmtiClassDefinition,
+    ) -> jvmtiError,
+    pub GetVersionNumber:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, version_ptr: *mut jint) -> jvmtiError,
+    pub GetCapabilities: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        capabilities_ptr: *mut jvmtiCapabilities,
+    ) -> jvmtiError,
+    pub GetSourceDebugExtension: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        source_debug_extension_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub IsMethodObsolete: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        method: jmethodID,
+        is_obsolete_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub SuspendThreadList: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        request_count: jint,
+        request_list: *const jthread,
+        results: *mut jvmtiError,
+    ) -> jvmtiError,
+    pub ResumeThreadList: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        request_count: jint,
+        request_list: *const jthread,
+        results: *mut jvmtiError,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub AddModuleReads: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module: jobject,
+        to_module: jobject,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub AddModuleExports: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module: jobject,
+        pkg_name: *const c_char,
+        to_module: jobject,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub AddModuleOpens: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module: jobject,
+        pkg_name: *const c_char,
+        to_module: jobject,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub AddModuleUses: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module: jobject,
+        service: jclass,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub AddModuleProvides: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module: jobject,
+        service: jclass,
+        impl_class: jclass,
+    ) -> jvmtiError,
+    #[jni_added("9")]
+    pub IsModifiableModule: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        module: jobject,
+        is_modifiable_module_ptr: *mut jboolean,
+    ) -> jvmtiError,
+    pub GetAllStackTraces: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        max_frame_count: jint,
+        stack_info_ptr: *mut *mut jvmtiStackInfo,
+        thread_count_ptr: *mut jint,
+    ) -> jvmtiError,
+    pub GetThreadListStackTraces: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread_count: jint,
+        thread_list: *const jthread,
+        max_frame_count: jint,
+        stack_info_ptr: *mut *mut jvmtiStackInfo,
+    ) -> jvmtiError,
+    pub GetThreadLocalStorage: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        data_ptr: *mut *mut c_void,
+    ) -> jvmtiError,
+    pub SetThreadLocalStorage: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        data: *const c_void,
+    ) -> jvmtiError,
+    pub GetStackTrace: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        start_depth: jint,
+        max_frame_count: jint,
+        frame_buffer: *mut jvmtiFrameInfo,
+        count_ptr: *mut jint,
+    ) -> jvmtiError,
+    #[jni_added("reserved")]
+    pub reserved105: *mut c_void,
+    pub GetTag: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        object: jobject,
+        tag_ptr: *mut jlong,
+    ) -> jvmtiError,
+    pub SetTag:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, object: jobject, tag: jlong) -> jvmtiError,
+    pub ForceGarbageCollection: unsafe extern "system" fn(env: *mut jvmtiEnv) -> jvmtiError,
+    pub IterateOverObjectsReachableFromObject: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        object: jobject,
+        object_reference_callback: jvmtiObjectReferenceCallback,
+        user_data: *const c_void,
+    ) -> jvmtiError,
+    pub IterateOverReachableObjects: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        heap_root_callback: jvmtiHeapRootCallback,
+        stack_ref_callback: jvmtiStackReferenceCallback,
+        object_ref_callback: jvmtiObjectReferenceCallback,
+        user_data: *const c_void,
+    ) -> jvmtiError,
+    pub IterateOverHeap: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        object_filter: jvmtiHeapObjectFilter,
+        heap_object_callback: jvmtiHeapObjectCallback,
+        user_data: *const c_void,
+    ) -> jvmtiError,
+    pub IterateOverInstancesOfClass: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        object_filter: jvmtiHeapObjectFilter,
+        heap_object_callback: jvmtiHeapObjectCallback,
+        user_data: *const c_void,
+    ) -> jvmtiError,
+    #[jni_added("reserved")]
+    pub reserved113: *mut c_void,
+    pub GetObjectsWithTags: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        tag_count: jint,
+        tags: *const jlong,
+        count_ptr: *mut jint,
+        object_result_ptr: *mut *mut jobject,
+        tag_result_ptr: *mut *mut jlong,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub FollowReferences: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        heap_filter: jint,
+        klass: jclass,
+        initial_object: jobject,
+        callbacks: *const jvmtiHeapCallbacks,
+        user_data: *const c_void,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub IterateThroughHeap: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        heap_filter: jint,
+        klass: jclass,
+        callbacks: *const jvmtiHeapCallbacks,
+        user_data: *const c_void,
+    ) -> jvmtiError,
+    #[jni_added("reserved")]
+    pub reserved117: *mut c_void,
+    #[jni_added("21")]
+    pub SuspendAllVirtualThreads: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        except_count: jint,
+        except_list: *const jthread,
+    ) -> jvmtiError,
+    #[jni_added("21")]
+    pub ResumeAllVirtualThreads: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        except_count: jint,
+        except_list: *const jthread,
+    ) -> jvmtiError,
+    pub SetJNIFunctionTable: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        function_table: *const jniNativeInterface,
+    ) -> jvmtiError,
+    pub GetJNIFunctionTable: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        function_table: *mut *mut jniNativeInterface,
+    ) -> jvmtiError,
+    pub SetEventCallbacks: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        callbacks: *const jvmtiEventCallbacks,
+        size_of_callbacks: jint,
+    ) -> jvmtiError,
+    pub GenerateEvents:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, event_type: jvmtiEvent) -> jvmtiError,
+    pub GetExtensionFunctions: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        extension_count_ptr: *mut jint,
+        extensions: *mut *mut jvmtiExtensionFunctionInfo,
+    ) -> jvmtiError,
+    pub GetExtensionEvents: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        extension_count_ptr: *mut jint,
+        extensions: *mut *mut jvmtiExtensionEventInfo,
+    ) -> jvmtiError,
+    pub SetExtensionEventCallback: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        extension_event_index: jint,
+        callback: jvmtiExtensionEvent,
+    ) -> jvmtiError,
+    pub DisposeEnvironment: unsafe extern "system" fn(env: *mut jvmtiEnv) -> jvmtiError,
+    pub GetErrorName: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        error: jvmtiError,
+        name_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetJLocationFormat: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        format_ptr: *mut jvmtiJlocationFormat,
+    ) -> jvmtiError,
+    pub GetSystemProperties: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        count_ptr: *mut jint,
+        property_ptr: *mut *mut *mut c_char,
+    ) -> jvmtiError,
+    pub GetSystemProperty: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        property: *const c_char,
+        value_ptr: *mut *mut c_char,
+    ) -> jvmtiError,
+    pub SetSystemProperty: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        property: *const c_char,
+        value_ptr: *const c_char,
+    ) -> jvmtiError,
+    pub GetPhase:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, phase_ptr: *mut jvmtiPhase) -> jvmtiError,
+    pub GetCurrentThreadCpuTimerInfo:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, info_ptr: *mut jvmtiTimerInfo) -> jvmtiError,
+    pub GetCurrentThreadCpuTime:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, nanos_ptr: *mut jlong) -> jvmtiError,
+    pub GetThreadCpuTimerInfo:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, info_ptr: *mut jvmtiTimerInfo) -> jvmtiError,
+    pub GetThreadCpuTime: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        nanos_ptr: *mut jlong,
+    ) -> jvmtiError,
+    pub GetTimerInfo:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, info_ptr: *mut jvmtiTimerInfo) -> jvmtiError,
+    pub GetTime: unsafe extern "system" fn(env: *mut jvmtiEnv, nanos_ptr: *mut jlong) -> jvmtiError,
+    pub GetPotentialCapabilities: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        capabilities_ptr: *mut jvmtiCapabilities,
+    ) -> jvmtiError,
+    #[jni_added("reserved")]
+    pub reserved141: *mut c_void,
+    pub AddCapabilities: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        capabilities_ptr: *const jvmtiCapabilities,
+    ) -> jvmtiError,
+    pub RelinquishCapabilities: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        capabilities_ptr: *const jvmtiCapabilities,
+    ) -> jvmtiError,
+    pub GetAvailableProcessors:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, processor_count_ptr: *mut jint) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub GetClassVersionNumbers: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        minor_version_ptr: *mut jint,
+        major_version_ptr: *mut jint,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub GetConstantPool: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        klass: jclass,
+        constant_pool_count_ptr: *mut jint,
+        constant_pool_byte_count_ptr: *mut jint,
+        constant_pool_bytes_ptr: *mut *mut c_uchar,
+    ) -> jvmtiError,
+    pub GetEnvironmentLocalStorage:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, data_ptr: *mut *mut c_void) -> jvmtiError,
+    pub SetEnvironmentLocalStorage:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, data: *const c_void) -> jvmtiError,
+    pub AddToBootstrapClassLoaderSearch:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, segment: *const c_char) -> jvmtiError,
+    pub SetVerboseFlag: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        flag: jvmtiVerboseFlag,
+        value: jboolean,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub AddToSystemClassLoaderSearch:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, segment: *const c_char) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub RetransformClasses: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        class_count: jint,
+        classes: *const jclass,
+    ) -> jvmtiError,
+    #[jni_added("1.1")]
+    pub GetOwnedMonitorStackDepthInfo: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        monitor_info_count_ptr: *mut jint,
+        monitor_info_ptr: *mut *mut jvmtiMonitorStackDepthInfo,
+    ) -> jvmtiError,
+    pub GetObjectSize: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        object: jobject,
+        size_ptr: *mut jlong,
+    ) -> jvmtiError,
+    #[jni_added("1.2")]
+    pub GetLocalInstance: unsafe extern "system" fn(
+        env: *mut jvmtiEnv,
+        thread: jthread,
+        depth: jint,
+        value_ptr: *mut jobject,
+    ) -> jvmtiError,
+    #[jni_added("11")]
+    pub SetHeapSamplingInterval:
+        unsafe extern "system" fn(env: *mut jvmtiEnv, sampling_interval: jint) -> jvmtiError,
+}
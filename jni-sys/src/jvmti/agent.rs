@@ -0,0 +1,299 @@
+//! Agent entry points, options and environments
+//!
+//! The VM calls `Agent_OnLoad` for an agent given with `-agentlib` or `-agentpath`, or
+//! `Agent_OnAttach` when an agent is loaded into a running VM, and `Agent_OnUnload` when the
+//! agent is unloaded. An agent linked statically into the VM exports `Agent_OnLoad_<libname>`,
+//! `Agent_OnAttach_<libname>` and `Agent_OnUnload_<libname>` instead.
+//!
+//! [`agent_on_load!`](crate::agent_on_load), [`agent_on_attach!`](crate::agent_on_attach) and
+//! [`agent_on_unload!`](crate::agent_on_unload) export a Rust function under these names with
+//! the right ABI, and record the phase the VM called them in for [`entry_phase`]. The options
+//! string of an agent is parsed with [`AgentOptions`], and [`get_jvmti_env`] gets its
+//! environment. The current phase of the VM is given by [`current_phase`].
+
+use core::ffi::{c_char, c_void, CStr};
+use core::ptr;
+use core::str::{self, Utf8Error};
+use core::sync::atomic::{AtomicI32, Ordering};
+
+use super::{jvmtiEnv, jvmtiError, jvmtiPhase};
+use crate::invocation::JniError;
+use crate::{jint, JavaVM};
+
+/// The type of `Agent_OnLoad`, `Agent_OnAttach` and their `_<libname>` variants
+///
+/// Returns 0 on success, or else an error that fails the VM startup (for `Agent_OnLoad`) or the
+/// attach (for `Agent_OnAttach`).
+pub type Agent_OnLoad_fn =
+    unsafe extern "system" fn(vm: *mut JavaVM, options: *mut c_char, reserved: *mut c_void) -> jint;
+
+/// The type of `Agent_OnAttach` and `Agent_OnAttach_<libname>`
+pub type Agent_OnAttach_fn = Agent_OnLoad_fn;
+
+/// The type of `Agent_OnUnload` and `Agent_OnUnload_<libname>`
+pub type Agent_OnUnload_fn = unsafe extern "system" fn(vm: *mut JavaVM);
+
+static PHASE: AtomicI32 = AtomicI32::new(0);
+
+/// Records `phase` as the phase of the VM when an agent entry point was called
+///
+/// This is called by the entry points declared with the macros.
+pub fn record_entry_phase(phase: jvmtiPhase) {
+    PHASE.store(phase, Ordering::Relaxed);
+}
+
+/// Returns the phase of the VM when an agent entry point declared with the macros was last
+/// called, or `None` if none has been
+///
+/// This is `JVMTI_PHASE_ONLOAD` from `Agent_OnLoad`, `JVMTI_PHASE_LIVE` from `Agent_OnAttach`
+/// and `JVMTI_PHASE_DEAD` from `Agent_OnUnload`. It isn't updated as the VM moves on, such as
+/// from the `OnLoad` phase once `Agent_OnLoad` returns; [`current_phase`] gives the phase the
+/// VM is in.
+pub fn entry_phase() -> Option<jvmtiPhase> {
+    match PHASE.load(Ordering::Relaxed) {
+        0 => None,
+        phase => Some(phase),
+    }
+}
+
+/// Returns the current phase of the VM with `GetPhase`
+///
+/// # Safety
+///
+/// `env` must be a valid JVMTI environment.
+pub unsafe fn current_phase(env: *mut jvmtiEnv) -> Result<jvmtiPhase, jvmtiError> {
+    let mut phase = 0;
+    ((**env).v1.GetPhase)(env, &mut phase).check()?;
+    Ok(phase)
+}
+
+/// Gets a new JVMTI environment from `GetEnv` with `version`, one of the `JVMTI_VERSION_*`
+/// constants
+///
+/// Fails with [`JniError::Version`] if the VM doesn't support `version`.
+///
+/// # Safety
+///
+/// `vm` must be a valid VM that supports at least JNI 1.2. Outside of `Agent_OnLoad`, the
+/// current thread must be attached to it.
+pub unsafe fn get_jvmti_env(vm: *mut JavaVM, version: jint) -> Result<*mut jvmtiEnv, JniError> {
+    let mut env: *mut c_void = ptr::null_mut();
+    JniError::check(((**vm).v1_2.GetEnv)(vm, &mut env, version))?;
+    Ok(env.cast())
+}
+
+/// The options string of an agent, a comma separated list of `key=value` options and flags
+///
+/// This is the convention of the JDK's own agents, such as
+/// `-agentlib:jdwp=transport=dt_socket,server=y`. Empty options are skipped, and a flag has no
+/// value.
+///
+/// ```
+/// use jni_sys::jvmti::agent::AgentOptions;
+///
+/// let options = AgentOptions::new("depth=8,verbose,,out=a=b");
+/// assert_eq!(options.value("depth"), Some("8"));
+/// assert_eq!(options.value("out"), Some("a=b"));
+/// assert!(options.flag("verbose"));
+/// assert!(!options.flag("depth"));
+/// assert_eq!(options.iter().count(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AgentOptions<'a> {
+    options: &'a str,
+}
+
+impl<'a> AgentOptions<'a> {
+    /// Returns the options in `options`
+    pub const fn new(options: &'a str) -> Self {
+        Self { options }
+    }
+
+    /// Returns the options passed to an entry point, which are empty if `options` is null
+    ///
+    /// # Safety
+    ///
+    /// `options` must be null or a valid C string that lives for `'a`.
+    pub unsafe fn from_ptr(options: *const c_char) -> Result<Self, Utf8Error> {
+        if options.is_null() {
+            return Ok(Self::default());
+        }
+        CStr::from_ptr(options).to_str().map(Self::new)
+    }
+
+    /// Returns the whole options string
+    pub const fn as_str(&self) -> &'a str {
+        self.options
+    }
+
+    /// Returns whether there are no options
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Iterates over the options as keys and values, which are `None` for flags
+    ///
+    /// An option is split at its first `=`.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, Option<&'a str>)> {
+        self.options
+            .split(',')
+            .filter(|option| !option.is_empty())
+            .map(|option| match option.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (option, None),
+            })
+    }
+
+    /// Returns the value of the last `key=value` option, if any
+    pub fn value(&self, key: &str) -> Option<&'a str> {
+        self.iter()
+            .filter(|(k, _)| *k == key)
+            .filter_map(|(_, value)| value)
+            .last()
+    }
+
+    /// Returns whether `key` is given as a flag, without a value
+    pub fn flag(&self, key: &str) -> bool {
+        self.iter().any(|option| option == (key, None))
+    }
+}
+
+/// Exports a function as `Agent_OnLoad`, or as `Agent_OnLoad_<libname>` if a library name is
+/// given
+///
+/// The function takes the VM, the options string (which may be null) and the reserved pointer,
+/// and returns 0 on success. The phase is recorded as `JVMTI_PHASE_ONLOAD` for
+/// [`agent::entry_phase`](crate::jvmti::agent::entry_phase).
+///
+/// ```
+/// use jni_sys::jvmti::agent::{get_jvmti_env, AgentOptions};
+/// use jni_sys::jvmti::JVMTI_VERSION_1_2;
+/// use jni_sys::{jint, JavaVM, JNI_ERR};
+/// use std::ffi::{c_char, c_void};
+///
+/// unsafe fn on_load(vm: *mut JavaVM, options: *mut c_char, _reserved: *mut c_void) -> jint {
+///     let Ok(_options) = AgentOptions::from_ptr(options) else {
+///         return JNI_ERR;
+///     };
+///     match get_jvmti_env(vm, JVMTI_VERSION_1_2) {
+///         Ok(_jvmti) => 0,
+///         Err(_) => JNI_ERR,
+///     }
+/// }
+///
+/// jni_sys::agent_on_load!(on_load);
+/// jni_sys::agent_on_load!(on_load, "myagent");
+/// ```
+#[macro_export]
+macro_rules! agent_on_load {
+    ($on_load:path) => {
+        $crate::agent_on_load!(
+            @export Agent_OnLoad,
+            "Agent_OnLoad",
+            $on_load,
+            JVMTI_PHASE_ONLOAD
+        );
+    };
+    ($on_load:path, $libname:literal) => {
+        $crate::agent_on_load!(
+            @export on_load_static,
+            concat!("Agent_OnLoad_", $libname),
+            $on_load,
+            JVMTI_PHASE_ONLOAD
+        );
+    };
+    (@export $name:ident, $symbol:expr, $on_load:path, $phase:ident) => {
+        const _: () = {
+            #[export_name = $symbol]
+            unsafe extern "system" fn $name(
+                vm: *mut $crate::JavaVM,
+                options: *mut ::core::ffi::c_char,
+                reserved: *mut ::core::ffi::c_void,
+            ) -> $crate::jint {
+                let on_load: unsafe fn(
+                    *mut $crate::JavaVM,
+                    *mut ::core::ffi::c_char,
+                    *mut ::core::ffi::c_void,
+                ) -> $crate::jint = $on_load;
+                $crate::jvmti::agent::record_entry_phase($crate::jvmti::$phase);
+                on_load(vm, options, reserved)
+            }
+            const _: $crate::jvmti::agent::Agent_OnLoad_fn = $name;
+        };
+    };
+}
+
+/// Exports a function as `Agent_OnAttach`, or as `Agent_OnAttach_<libname>` if a library name
+/// is given
+///
+/// The function has the same signature as for [`agent_on_load!`](crate::agent_on_load), and is
+/// called in the live phase, which is recorded as `JVMTI_PHASE_LIVE`.
+///
+/// ```
+/// use jni_sys::{jint, JavaVM};
+/// use std::ffi::{c_char, c_void};
+///
+/// fn on_attach(_vm: *mut JavaVM, _options: *mut c_char, _reserved: *mut c_void) -> jint {
+///     0
+/// }
+///
+/// jni_sys::agent_on_attach!(on_attach);
+/// jni_sys::agent_on_attach!(on_attach, "myagent");
+/// ```
+#[macro_export]
+macro_rules! agent_on_attach {
+    ($on_attach:path) => {
+        $crate::agent_on_load!(
+            @export Agent_OnAttach,
+            "Agent_OnAttach",
+            $on_attach,
+            JVMTI_PHASE_LIVE
+        );
+    };
+    ($on_attach:path, $libname:literal) => {
+        $crate::agent_on_load!(
+            @export on_attach_static,
+            concat!("Agent_OnAttach_", $libname),
+            $on_attach,
+            JVMTI_PHASE_LIVE
+        );
+    };
+}
+
+/// Exports a function as `Agent_OnUnload`, or as `Agent_OnUnload_<libname>` if a library name
+/// is given
+///
+/// The function takes the VM. The phase is recorded as `JVMTI_PHASE_DEAD` before it's called.
+///
+/// ```
+/// use jni_sys::JavaVM;
+///
+/// fn on_unload(_vm: *mut JavaVM) {}
+///
+/// jni_sys::agent_on_unload!(on_unload);
+/// jni_sys::agent_on_unload!(on_unload, "myagent");
+/// ```
+#[macro_export]
+macro_rules! agent_on_unload {
+    ($on_unload:path) => {
+        $crate::agent_on_unload!(@export Agent_OnUnload, "Agent_OnUnload", $on_unload);
+    };
+    ($on_unload:path, $libname:literal) => {
+        $crate::agent_on_unload!(
+            @export on_unload_static,
+            concat!("Agent_OnUnload_", $libname),
+            $on_unload
+        );
+    };
+    (@export $name:ident, $symbol:expr, $on_unload:path) => {
+        const _: () = {
+            #[export_name = $symbol]
+            unsafe extern "system" fn $name(vm: *mut $crate::JavaVM) {
+                let on_unload: unsafe fn(*mut $crate::JavaVM) = $on_unload;
+                $crate::jvmti::agent::record_entry_phase($crate::jvmti::JVMTI_PHASE_DEAD);
+                on_unload(vm);
+            }
+            const _: $crate::jvmti::agent::Agent_OnUnload_fn = $name;
+        };
+    };
+}
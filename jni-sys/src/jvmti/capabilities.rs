@@ -0,0 +1,297 @@
+//! Accessors for the [`jvmtiCapabilities`] bitfield
+//!
+//! In C, `jvmtiCapabilities` is a struct of one bit `unsigned int` bitfields padded to 128 bits.
+//! Its Rust definition is the four underlying words, and the capabilities are read and written
+//! with a getter and setter per capability, or by [`Capability`].
+
+use core::ffi::c_uint;
+use core::fmt;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+use super::jvmtiCapabilities;
+
+macro_rules! capabilities {
+    ($($(#[$meta:meta])* $bit:literal => $variant:ident, $get:ident, $set:ident;)*) => {
+        /// A capability of a JVMTI environment, numbered by its bit in [`jvmtiCapabilities`]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(u8)]
+        pub enum Capability {
+            $(
+            #[doc = concat!("`", stringify!($get), "`")]
+            $(#[$meta])*
+            $variant = $bit,
+            )*
+        }
+
+        impl Capability {
+            /// Every capability, in bit order
+            pub const ALL: &'static [Capability] = &[$(Capability::$variant),*];
+
+            /// Returns the name of the capability's field in `jvmti.h`, like `can_tag_objects`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Capability::$variant => stringify!($get),)*
+                }
+            }
+        }
+
+        impl jvmtiCapabilities {
+            $(
+            #[doc = concat!("Returns whether `", stringify!($get), "` is set")]
+            pub fn $get(&self) -> bool {
+                self.has(Capability::$variant)
+            }
+
+            #[doc = concat!("Sets or clears `", stringify!($get), "`")]
+            pub fn $set(&mut self, value: bool) {
+                self.set(Capability::$variant, value)
+            }
+            )*
+        }
+    };
+}
+
+capabilities! {
+    0 => CanTagObjects, can_tag_objects, set_can_tag_objects;
+    1 => CanGenerateFieldModificationEvents, can_generate_field_modification_events,
+        set_can_generate_field_modification_events;
+    2 => CanGenerateFieldAccessEvents, can_generate_field_access_events,
+        set_can_generate_field_access_events;
+    3 => CanGetBytecodes, can_get_bytecodes, set_can_get_bytecodes;
+    4 => CanGetSyntheticAttribute, can_get_synthetic_attribute, set_can_get_synthetic_attribute;
+    5 => CanGetOwnedMonitorInfo, can_get_owned_monitor_info, set_can_get_owned_monitor_info;
+    6 => CanGetCurrentContendedMonitor, can_get_current_contended_monitor,
+        set_can_get_current_contended_monitor;
+    7 => CanGetMonitorInfo, can_get_monitor_info, set_can_get_monitor_info;
+    8 => CanPopFrame, can_pop_frame, set_can_pop_frame;
+    9 => CanRedefineClasses, can_redefine_classes, set_can_redefine_classes;
+    10 => CanSignalThread, can_signal_thread, set_can_signal_thread;
+    11 => CanGetSourceFileName, can_get_source_file_name, set_can_get_source_file_name;
+    12 => CanGetLineNumbers, can_get_line_numbers, set_can_get_line_numbers;
+    13 => CanGetSourceDebugExtension, can_get_source_debug_extension,
+        set_can_get_source_debug_extension;
+    14 => CanAccessLocalVariables, can_access_local_variables, set_can_access_local_variables;
+    15 => CanMaintainOriginalMethodOrder, can_maintain_original_method_order,
+        set_can_maintain_original_method_order;
+    16 => CanGenerateSingleStepEvents, can_generate_single_step_events,
+        set_can_generate_single_step_events;
+    17 => CanGenerateExceptionEvents, can_generate_exception_events,
+        set_can_generate_exception_events;
+    18 => CanGenerateFramePopEvents, can_generate_frame_pop_events,
+        set_can_generate_frame_pop_events;
+    19 => CanGenerateBreakpointEvents, can_generate_breakpoint_events,
+        set_can_generate_breakpoint_events;
+    20 => CanSuspend, can_suspend, set_can_suspend;
+    21 => CanRedefineAnyClass, can_redefine_any_class, set_can_redefine_any_class;
+    22 => CanGetCurrentThreadCpuTime, can_get_current_thread_cpu_time,
+        set_can_get_current_thread_cpu_time;
+    23 => CanGetThreadCpuTime, can_get_thread_cpu_time, set_can_get_thread_cpu_time;
+    24 => CanGenerateMethodEntryEvents, can_generate_method_entry_events,
+        set_can_generate_method_entry_events;
+    25 => CanGenerateMethodExitEvents, can_generate_method_exit_events,
+        set_can_generate_method_exit_events;
+    26 => CanGenerateAllClassHookEvents, can_generate_all_class_hook_events,
+        set_can_generate_all_class_hook_events;
+    27 => CanGenerateCompiledMethodLoadEvents, can_generate_compiled_method_load_events,
+        set_can_generate_compiled_method_load_events;
+    28 => CanGenerateMonitorEvents, can_generate_monitor_events, set_can_generate_monitor_events;
+    29 => CanGenerateVmObjectAllocEvents, can_generate_vm_object_alloc_events,
+        set_can_generate_vm_object_alloc_events;
+    30 => CanGenerateNativeMethodBindEvents, can_generate_native_method_bind_events,
+        set_can_generate_native_method_bind_events;
+    31 => CanGenerateGarbageCollectionEvents, can_generate_garbage_collection_events,
+        set_can_generate_garbage_collection_events;
+    32 => CanGenerateObjectFreeEvents, can_generate_object_free_events,
+        set_can_generate_object_free_events;
+    33 => CanForceEarlyReturn, can_force_early_return, set_can_force_early_return;
+    34 => CanGetOwnedMonitorStackDepthInfo, can_get_owned_monitor_stack_depth_info,
+        set_can_get_owned_monitor_stack_depth_info;
+    35 => CanGetConstantPool, can_get_constant_pool, set_can_get_constant_pool;
+    36 => CanSetNativeMethodPrefix, can_set_native_method_prefix, set_can_set_native_method_prefix;
+    37 => CanRetransformClasses, can_retransform_classes, set_can_retransform_classes;
+    38 => CanRetransformAnyClass, can_retransform_any_class, set_can_retransform_any_class;
+    39 => CanGenerateResourceExhaustionHeapEvents, can_generate_resource_exhaustion_heap_events,
+        set_can_generate_resource_exhaustion_heap_events;
+    40 => CanGenerateResourceExhaustionThreadsEvents,
+        can_generate_resource_exhaustion_threads_events,
+        set_can_generate_resource_exhaustion_threads_events;
+    41 => CanGenerateEarlyVmstart, can_generate_early_vmstart, set_can_generate_early_vmstart;
+    42 => CanGenerateEarlyClassHookEvents, can_generate_early_class_hook_events,
+        set_can_generate_early_class_hook_events;
+    43 => CanGenerateSampledObjectAllocEvents, can_generate_sampled_object_alloc_events,
+        set_can_generate_sampled_object_alloc_events;
+    ///
+    /// Added in JVMTI 21.
+    44 => CanSupportVirtualThreads, can_support_virtual_threads, set_can_support_virtual_threads;
+}
+
+impl Capability {
+    /// Returns the index of the capability's word, and its mask in that word
+    fn word_mask(self) -> (usize, c_uint) {
+        let bit = self as u32;
+        // Bitfields are allocated from the least significant bit of each word on little
+        // endian targets, and from the most significant bit on big endian targets
+        let shift = if cfg!(target_endian = "big") {
+            31 - bit % 32
+        } else {
+            bit % 32
+        };
+        ((bit / 32) as usize, 1 << shift)
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl jvmtiCapabilities {
+    /// Returns capabilities with nothing set
+    pub const fn new() -> Self {
+        Self { bits: [0; 4] }
+    }
+
+    /// Returns whether `capability` is set
+    pub fn has(&self, capability: Capability) -> bool {
+        let (word, mask) = capability.word_mask();
+        self.bits[word] & mask != 0
+    }
+
+    /// Sets or clears `capability`
+    pub fn set(&mut self, capability: Capability, value: bool) {
+        let (word, mask) = capability.word_mask();
+        if value {
+            self.bits[word] |= mask;
+        } else {
+            self.bits[word] &= !mask;
+        }
+    }
+
+    /// Returns these capabilities with `capability` set
+    #[must_use]
+    pub fn with(mut self, capability: Capability) -> Self {
+        self.set(capability, true);
+        self
+    }
+
+    /// Returns whether no capability is set
+    ///
+    /// Bits that aren't a known [`Capability`] count too.
+    pub fn is_empty(&self) -> bool {
+        self.bits == [0; 4]
+    }
+
+    /// Returns whether every capability set in `self` is also set in `other`
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Returns the capabilities set in either `self` or `other`
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a | b)
+    }
+
+    /// Returns the capabilities set in both `self` and `other`
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a & b)
+    }
+
+    /// Returns the capabilities set in `self` but not in `other`
+    ///
+    /// For example, the difference between the wanted capabilities and those from
+    /// `GetPotentialCapabilities` is what an agent can't have.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a & !b)
+    }
+
+    /// Iterates over the set capabilities, in bit order
+    pub fn iter(&self) -> impl Iterator<Item = Capability> {
+        let capabilities = *self;
+        Capability::ALL
+            .iter()
+            .copied()
+            .filter(move |capability| capabilities.has(*capability))
+    }
+
+    fn zip(&self, other: &Self, f: impl Fn(c_uint, c_uint) -> c_uint) -> Self {
+        let mut bits = [0; 4];
+        for (i, bits) in bits.iter_mut().enumerate() {
+            *bits = f(self.bits[i], other.bits[i]);
+        }
+        Self { bits }
+    }
+}
+
+impl fmt::Debug for jvmtiCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Capability> for jvmtiCapabilities {
+    fn from_iter<T: IntoIterator<Item = Capability>>(iter: T) -> Self {
+        let mut capabilities = Self::new();
+        capabilities.extend(iter);
+        capabilities
+    }
+}
+
+impl Extend<Capability> for jvmtiCapabilities {
+    fn extend<T: IntoIterator<Item = Capability>>(&mut self, iter: T) {
+        for capability in iter {
+            self.set(capability, true);
+        }
+    }
+}
+
+impl From<Capability> for jvmtiCapabilities {
+    fn from(capability: Capability) -> Self {
+        Self::new().with(capability)
+    }
+}
+
+impl BitOr for jvmtiCapabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(&rhs)
+    }
+}
+
+impl BitOrAssign for jvmtiCapabilities {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(&rhs);
+    }
+}
+
+impl BitAnd for jvmtiCapabilities {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(&rhs)
+    }
+}
+
+impl BitAndAssign for jvmtiCapabilities {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(&rhs);
+    }
+}
+
+impl Sub for jvmtiCapabilities {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(&rhs)
+    }
+}
+
+impl SubAssign for jvmtiCapabilities {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.difference(&rhs);
+    }
+}
@@ -0,0 +1,138 @@
+//! Names, descriptions and checking of [`jvmtiError`] codes
+//!
+//! Every JVMTI function returns a `jvmtiError`, which is `JVMTI_ERROR_NONE` on success.
+//! [`jvmtiError::check`] turns it into a `Result`.
+
+use core::fmt;
+
+use super::*;
+
+macro_rules! errors {
+    ($($error:ident => $description:literal;)*) => {
+        impl jvmtiError {
+            /// Returns the name of the error's constant, like `JVMTI_ERROR_WRONG_PHASE`, or `None`
+            /// if the error is unknown
+            pub const fn name(self) -> Option<&'static str> {
+                match self {
+                    $($error => Some(stringify!($error)),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns a short description of the error, or `None` if the error is unknown
+            pub const fn description(self) -> Option<&'static str> {
+                match self {
+                    $($error => Some($description),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+errors! {
+    JVMTI_ERROR_NONE => "no error";
+    JVMTI_ERROR_INVALID_THREAD => "invalid thread";
+    JVMTI_ERROR_INVALID_THREAD_GROUP => "invalid thread group";
+    JVMTI_ERROR_INVALID_PRIORITY => "invalid priority";
+    JVMTI_ERROR_THREAD_NOT_SUSPENDED => "thread not suspended";
+    JVMTI_ERROR_THREAD_SUSPENDED => "thread already suspended";
+    JVMTI_ERROR_THREAD_NOT_ALIVE => "thread not started or already terminated";
+    JVMTI_ERROR_INVALID_OBJECT => "invalid object";
+    JVMTI_ERROR_INVALID_CLASS => "invalid class";
+    JVMTI_ERROR_CLASS_NOT_PREPARED => "class loaded but not yet prepared";
+    JVMTI_ERROR_INVALID_METHODID => "invalid method";
+    JVMTI_ERROR_INVALID_LOCATION => "invalid location";
+    JVMTI_ERROR_INVALID_FIELDID => "invalid field";
+    JVMTI_ERROR_INVALID_MODULE => "invalid module";
+    JVMTI_ERROR_NO_MORE_FRAMES => "no stack frame at the specified depth";
+    JVMTI_ERROR_OPAQUE_FRAME => "information about the frame is not available";
+    JVMTI_ERROR_TYPE_MISMATCH => "variable type does not match the function";
+    JVMTI_ERROR_INVALID_SLOT => "invalid slot";
+    JVMTI_ERROR_DUPLICATE => "item already set";
+    JVMTI_ERROR_NOT_FOUND => "element not found";
+    JVMTI_ERROR_INVALID_MONITOR => "invalid raw monitor";
+    JVMTI_ERROR_NOT_MONITOR_OWNER => "thread does not own the raw monitor";
+    JVMTI_ERROR_INTERRUPT => "call interrupted before completion";
+    JVMTI_ERROR_INVALID_CLASS_FORMAT => "malformed class file";
+    JVMTI_ERROR_CIRCULAR_CLASS_DEFINITION => "circular class definition";
+    JVMTI_ERROR_FAILS_VERIFICATION => "class bytes fail verification";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_METHOD_ADDED => "redefinition would add a method";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_SCHEMA_CHANGED => "redefinition would change a field";
+    JVMTI_ERROR_INVALID_TYPESTATE => "thread state modified and now inconsistent";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_HIERARCHY_CHANGED =>
+        "redefinition would change the superclass or interfaces";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_METHOD_DELETED => "redefinition would delete a method";
+    JVMTI_ERROR_UNSUPPORTED_VERSION => "unsupported class file version";
+    JVMTI_ERROR_NAMES_DONT_MATCH => "class name does not match the redefined class";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_CLASS_MODIFIERS_CHANGED =>
+        "redefinition would change the class modifiers";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_METHOD_MODIFIERS_CHANGED =>
+        "redefinition would change method modifiers";
+    JVMTI_ERROR_UNSUPPORTED_REDEFINITION_CLASS_ATTRIBUTE_CHANGED =>
+        "redefinition would change class attributes";
+    JVMTI_ERROR_UNSUPPORTED_OPERATION => "unsupported operation";
+    JVMTI_ERROR_UNMODIFIABLE_CLASS => "class cannot be modified";
+    JVMTI_ERROR_UNMODIFIABLE_MODULE => "module cannot be modified";
+    JVMTI_ERROR_NOT_AVAILABLE => "functionality not available in this VM";
+    JVMTI_ERROR_MUST_POSSESS_CAPABILITY => "capability not possessed by this environment";
+    JVMTI_ERROR_NULL_POINTER => "unexpected null pointer";
+    JVMTI_ERROR_ABSENT_INFORMATION => "requested information not available";
+    JVMTI_ERROR_INVALID_EVENT_TYPE => "unknown event type";
+    JVMTI_ERROR_ILLEGAL_ARGUMENT => "illegal argument";
+    JVMTI_ERROR_NATIVE_METHOD => "information not available for a native method";
+    JVMTI_ERROR_CLASS_LOADER_UNSUPPORTED => "operation not supported by the class loader";
+    JVMTI_ERROR_OUT_OF_MEMORY => "out of memory";
+    JVMTI_ERROR_ACCESS_DENIED => "functionality not enabled in this VM";
+    JVMTI_ERROR_WRONG_PHASE => "functionality not available in the current phase";
+    JVMTI_ERROR_INTERNAL => "unexpected internal error";
+    JVMTI_ERROR_UNATTACHED_THREAD => "calling thread not attached to the VM";
+    JVMTI_ERROR_INVALID_ENVIRONMENT => "JVMTI environment no longer connected or invalid";
+}
+
+impl jvmtiError {
+    /// Returns the error with code `value`, which may not be a known error
+    pub const fn from_raw(value: jint) -> Self {
+        Self(value)
+    }
+
+    /// Returns the code of the error
+    pub const fn raw(self) -> jint {
+        self.0
+    }
+
+    /// Returns `Ok` for `JVMTI_ERROR_NONE`, or else the error
+    pub const fn check(self) -> Result<(), Self> {
+        match self {
+            JVMTI_ERROR_NONE => Ok(()),
+            error => Err(error),
+        }
+    }
+}
+
+impl From<jvmtiError> for jint {
+    fn from(error: jvmtiError) -> Self {
+        error.0
+    }
+}
+
+impl fmt::Debug for jvmtiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => f.debug_tuple("jvmtiError").field(&self.0).finish(),
+        }
+    }
+}
+
+impl fmt::Display for jvmtiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.description(), self.name()) {
+            (Some(description), Some(name)) => write!(f, "{description} ({name})"),
+            _ => write!(f, "JVMTI error {}", self.0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for jvmtiError {}
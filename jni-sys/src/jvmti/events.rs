@@ -0,0 +1,232 @@
+//! Event types and a builder for [`jvmtiEventCallbacks`]
+//!
+//! Events are enabled with `SetEventNotificationMode` and delivered to the callbacks passed to
+//! `SetEventCallbacks`, which takes the size of the callbacks struct so that the VM only reads
+//! the callbacks it knows about.
+
+use core::fmt;
+use core::mem;
+
+use super::*;
+
+macro_rules! events {
+    ($($(#[$meta:meta])* $event:ident => $field:ident: $callback:ident, $set:ident;)*) => {
+        impl jvmtiEvent {
+            /// Every event, in numeric order
+            pub const ALL: &'static [jvmtiEvent] = &[$($event),*];
+
+            /// Returns the name of the event's constant, like `JVMTI_EVENT_VM_INIT`, or `None` if
+            /// the event is unknown
+            pub fn name(self) -> Option<&'static str> {
+                match self {
+                    $($event => Some(stringify!($event)),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl EventCallbacks {
+            $(
+            #[doc = concat!(
+                "Sets the `", stringify!($field), "` callback, for `", stringify!($event), "`"
+            )]
+            $(#[$meta])*
+            pub const fn $set(mut self, callback: $callback) -> Self {
+                self.callbacks.$field = callback;
+                self
+            }
+            )*
+
+            /// Returns whether the callback for `event` is set
+            pub fn is_set(&self, event: jvmtiEvent) -> bool {
+                match event {
+                    $($event => self.callbacks.$field.is_some(),)*
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+events! {
+    JVMTI_EVENT_VM_INIT => VMInit: jvmtiEventVMInit, vm_init;
+    JVMTI_EVENT_VM_DEATH => VMDeath: jvmtiEventVMDeath, vm_death;
+    JVMTI_EVENT_THREAD_START => ThreadStart: jvmtiEventThreadStart, thread_start;
+    JVMTI_EVENT_THREAD_END => ThreadEnd: jvmtiEventThreadEnd, thread_end;
+    JVMTI_EVENT_CLASS_FILE_LOAD_HOOK => ClassFileLoadHook: jvmtiEventClassFileLoadHook,
+        class_file_load_hook;
+    JVMTI_EVENT_CLASS_LOAD => ClassLoad: jvmtiEventClassLoad, class_load;
+    JVMTI_EVENT_CLASS_PREPARE => ClassPrepare: jvmtiEventClassPrepare, class_prepare;
+    JVMTI_EVENT_VM_START => VMStart: jvmtiEventVMStart, vm_start;
+    JVMTI_EVENT_EXCEPTION => Exception: jvmtiEventException, exception;
+    JVMTI_EVENT_EXCEPTION_CATCH => ExceptionCatch: jvmtiEventExceptionCatch, exception_catch;
+    JVMTI_EVENT_SINGLE_STEP => SingleStep: jvmtiEventSingleStep, single_step;
+    JVMTI_EVENT_FRAME_POP => FramePop: jvmtiEventFramePop, frame_pop;
+    JVMTI_EVENT_BREAKPOINT => Breakpoint: jvmtiEventBreakpoint, breakpoint;
+    JVMTI_EVENT_FIELD_ACCESS => FieldAccess: jvmtiEventFieldAccess, field_access;
+    JVMTI_EVENT_FIELD_MODIFICATION => FieldModification: jvmtiEventFieldModification,
+        field_modification;
+    JVMTI_EVENT_METHOD_ENTRY => MethodEntry: jvmtiEventMethodEntry, method_entry;
+    JVMTI_EVENT_METHOD_EXIT => MethodExit: jvmtiEventMethodExit, method_exit;
+    JVMTI_EVENT_NATIVE_METHOD_BIND => NativeMethodBind: jvmtiEventNativeMethodBind,
+        native_method_bind;
+    JVMTI_EVENT_COMPILED_METHOD_LOAD => CompiledMethodLoad: jvmtiEventCompiledMethodLoad,
+        compiled_method_load;
+    JVMTI_EVENT_COMPILED_METHOD_UNLOAD => CompiledMethodUnload: jvmtiEventCompiledMethodUnload,
+        compiled_method_unload;
+    JVMTI_EVENT_DYNAMIC_CODE_GENERATED => DynamicCodeGenerated: jvmtiEventDynamicCodeGenerated,
+        dynamic_code_generated;
+    JVMTI_EVENT_DATA_DUMP_REQUEST => DataDumpRequest: jvmtiEventDataDumpRequest, data_dump_request;
+    JVMTI_EVENT_MONITOR_WAIT => MonitorWait: jvmtiEventMonitorWait, monitor_wait;
+    JVMTI_EVENT_MONITOR_WAITED => MonitorWaited: jvmtiEventMonitorWaited, monitor_waited;
+    JVMTI_EVENT_MONITOR_CONTENDED_ENTER => MonitorContendedEnter: jvmtiEventMonitorContendedEnter,
+        monitor_contended_enter;
+    JVMTI_EVENT_MONITOR_CONTENDED_ENTERED =>
+        MonitorContendedEntered: jvmtiEventMonitorContendedEntered, monitor_contended_entered;
+    JVMTI_EVENT_RESOURCE_EXHAUSTED => ResourceExhausted: jvmtiEventResourceExhausted,
+        resource_exhausted;
+    JVMTI_EVENT_GARBAGE_COLLECTION_START =>
+        GarbageCollectionStart: jvmtiEventGarbageCollectionStart, garbage_collection_start;
+    JVMTI_EVENT_GARBAGE_COLLECTION_FINISH =>
+        GarbageCollectionFinish: jvmtiEventGarbageCollectionFinish, garbage_collection_finish;
+    JVMTI_EVENT_OBJECT_FREE => ObjectFree: jvmtiEventObjectFree, object_free;
+    JVMTI_EVENT_VM_OBJECT_ALLOC => VMObjectAlloc: jvmtiEventVMObjectAlloc, vm_object_alloc;
+    JVMTI_EVENT_SAMPLED_OBJECT_ALLOC => SampledObjectAlloc: jvmtiEventSampledObjectAlloc,
+        sampled_object_alloc;
+    /// Added in JVMTI 21.
+    JVMTI_EVENT_VIRTUAL_THREAD_START => VirtualThreadStart: jvmtiEventVirtualThreadStart,
+        virtual_thread_start;
+    /// Added in JVMTI 21.
+    JVMTI_EVENT_VIRTUAL_THREAD_END => VirtualThreadEnd: jvmtiEventVirtualThreadEnd,
+        virtual_thread_end;
+}
+
+impl jvmtiEvent {
+    /// Returns the event numbered `value`, which may not be a known event
+    pub const fn from_raw(value: jint) -> Self {
+        Self(value)
+    }
+
+    /// Returns the number of the event
+    pub const fn raw(self) -> jint {
+        self.0
+    }
+}
+
+impl From<jvmtiEvent> for jint {
+    fn from(event: jvmtiEvent) -> Self {
+        event.0
+    }
+}
+
+impl fmt::Debug for jvmtiEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => f.debug_tuple("jvmtiEvent").field(&self.0).finish(),
+        }
+    }
+}
+
+/// A builder for the [`jvmtiEventCallbacks`] passed to `SetEventCallbacks`
+///
+/// ```
+/// use jni_sys::jvmti::events::EventCallbacks;
+/// use jni_sys::jvmti::{jthread, jvmtiEnv, JVMTI_EVENT_VM_INIT};
+/// use jni_sys::JNIEnv;
+///
+/// unsafe extern "system" fn vm_init(_jvmti: *mut jvmtiEnv, _jni: *mut JNIEnv, _thread: jthread) {}
+///
+/// let callbacks = EventCallbacks::new().vm_init(Some(vm_init));
+/// assert!(callbacks.is_set(JVMTI_EVENT_VM_INIT));
+/// assert_eq!(callbacks.events().collect::<Vec<_>>(), [JVMTI_EVENT_VM_INIT]);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EventCallbacks {
+    callbacks: jvmtiEventCallbacks,
+}
+
+impl EventCallbacks {
+    /// Returns callbacks with none set
+    pub const fn new() -> Self {
+        Self {
+            callbacks: jvmtiEventCallbacks {
+                VMInit: None,
+                VMDeath: None,
+                ThreadStart: None,
+                ThreadEnd: None,
+                ClassFileLoadHook: None,
+                ClassLoad: None,
+                ClassPrepare: None,
+                VMStart: None,
+                Exception: None,
+                ExceptionCatch: None,
+                SingleStep: None,
+                FramePop: None,
+                Breakpoint: None,
+                FieldAccess: None,
+                FieldModification: None,
+                MethodEntry: None,
+                MethodExit: None,
+                NativeMethodBind: None,
+                CompiledMethodLoad: None,
+                CompiledMethodUnload: None,
+                DynamicCodeGenerated: None,
+                DataDumpRequest: None,
+                reserved72: None,
+                MonitorWait: None,
+                MonitorWaited: None,
+                MonitorContendedEnter: None,
+                MonitorContendedEntered: None,
+                reserved77: None,
+                reserved78: None,
+                reserved79: None,
+                ResourceExhausted: None,
+                GarbageCollectionStart: None,
+                GarbageCollectionFinish: None,
+                ObjectFree: None,
+                VMObjectAlloc: None,
+                reserved85: None,
+                SampledObjectAlloc: None,
+                VirtualThreadStart: None,
+                VirtualThreadEnd: None,
+            },
+        }
+    }
+
+    /// Iterates over the events whose callbacks are set, in numeric order
+    ///
+    /// These are the events to enable with `SetEventNotificationMode`.
+    pub fn events(&self) -> impl Iterator<Item = jvmtiEvent> + '_ {
+        jvmtiEvent::ALL
+            .iter()
+            .copied()
+            .filter(move |event| self.is_set(*event))
+    }
+
+    /// Returns the callbacks
+    pub fn callbacks(&self) -> &jvmtiEventCallbacks {
+        &self.callbacks
+    }
+
+    /// Returns the `size_of_callbacks` argument of `SetEventCallbacks`
+    pub const fn size() -> jint {
+        mem::size_of::<jvmtiEventCallbacks>() as jint
+    }
+
+    /// Sets the callbacks of `env` with `SetEventCallbacks`
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid JVMTI environment, and the callbacks must be safe to call with
+    /// the arguments of their events.
+    pub unsafe fn set_event_callbacks(&self, env: *mut jvmtiEnv) -> Result<(), jvmtiError> {
+        ((**env).v1.SetEventCallbacks)(env, &self.callbacks, Self::size()).check()
+    }
+}
+
+impl From<EventCallbacks> for jvmtiEventCallbacks {
+    fn from(callbacks: EventCallbacks) -> Self {
+        callbacks.callbacks
+    }
+}
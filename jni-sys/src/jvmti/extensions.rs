@@ -0,0 +1,497 @@
+//! Discovery of extension functions and events
+//!
+//! A VM can offer functions and events beyond the JVMTI specification, such as HotSpot's
+//! `com.sun.hotspot.functions.IsClassUnloadingEnabled`. `GetExtensionFunctions` and
+//! `GetExtensionEvents` describe them, with the kind (`JVMTI_KIND_*`) and type (`JVMTI_TYPE_*`)
+//! of each parameter.
+//!
+//! [`Extensions`] owns these descriptions and finds them by id. An extension function is only
+//! called after its parameters are checked against an expected signature, through an
+//! [`ExtensionFn`] typed by its arguments.
+//!
+//! ```no_run
+//! use jni_sys::jboolean;
+//! use jni_sys::jvmti::extensions::{ExtensionFunctions, ParamSignature};
+//! use jni_sys::jvmti::{jvmtiEnv, JVMTI_KIND_OUT, JVMTI_TYPE_JBOOLEAN};
+//!
+//! unsafe fn is_class_unloading_enabled(jvmti: *mut jvmtiEnv) -> Option<bool> {
+//!     let functions = ExtensionFunctions::get(jvmti).ok()?;
+//!     let function = functions.find("com.sun.hotspot.functions.IsClassUnloadingEnabled")?;
+//!     let is_enabled = function
+//!         .typed::<(*mut jboolean,)>(&[ParamSignature::new(JVMTI_KIND_OUT, JVMTI_TYPE_JBOOLEAN)])
+//!         .ok()?;
+//!     let mut enabled = false;
+//!     is_enabled.call(jvmti, (&mut enabled,)).ok()?;
+//!     Some(enabled)
+//! }
+//! ```
+
+use core::ffi::{c_char, CStr};
+use core::fmt;
+use core::marker::PhantomData;
+use core::ptr;
+use core::slice;
+
+use super::memory::{checked_len, deallocate};
+use super::*;
+
+/// The kind and type of a parameter of an extension function or event
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParamSignature {
+    /// The kind of the parameter, one of the `JVMTI_KIND_*` constants
+    pub kind: jvmtiParamKind,
+    /// The base type of the parameter, one of the `JVMTI_TYPE_*` constants
+    pub base_type: jvmtiParamTypes,
+}
+
+impl ParamSignature {
+    /// Returns the signature of a parameter of `kind` with `base_type`
+    pub const fn new(kind: jvmtiParamKind, base_type: jvmtiParamTypes) -> Self {
+        Self { kind, base_type }
+    }
+}
+
+impl From<&jvmtiParamInfo> for ParamSignature {
+    fn from(param: &jvmtiParamInfo) -> Self {
+        Self::new(param.kind, param.base_type)
+    }
+}
+
+/// Error returned when an extension function doesn't have the expected signature
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The function has a different number of parameters
+    ParamCount {
+        /// The expected number of parameters
+        expected: usize,
+        /// The number of parameters of the function
+        found: usize,
+    },
+    /// The arguments type has a different number of arguments than the expected signature
+    ArgCount {
+        /// The number of parameters in the expected signature
+        expected: usize,
+        /// The number of arguments in the arguments type
+        found: usize,
+    },
+    /// A parameter has a different kind or type
+    Param {
+        /// The index of the parameter, not counting the `jvmtiEnv`
+        index: usize,
+        /// The expected signature
+        expected: ParamSignature,
+        /// The signature of the function's parameter
+        found: ParamSignature,
+    },
+    /// The function pointer is null
+    NullFunction,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParamCount { expected, found } => {
+                write!(f, "expected {expected} parameters, found {found}")
+            }
+            Self::ArgCount { expected, found } => write!(
+                f,
+                "the signature has {expected} parameters, but the arguments type has {found}"
+            ),
+            Self::Param {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected parameter {index} of kind {} and type {}, found kind {} and type {}",
+                expected.kind, expected.base_type, found.kind, found.base_type
+            ),
+            Self::NullFunction => f.write_str("null extension function"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignatureError {}
+
+/// An extension function or event description
+///
+/// This is implemented for [`jvmtiExtensionFunctionInfo`] and [`jvmtiExtensionEventInfo`].
+pub trait ExtensionInfo: Sized {
+    /// Calls `GetExtensionFunctions` or `GetExtensionEvents`
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid JVMTI environment.
+    unsafe fn get_all(env: *mut jvmtiEnv, count: *mut jint, infos: *mut *mut Self) -> jvmtiError;
+
+    /// Returns the id, the short description and the parameters
+    fn parts(&self) -> (*mut c_char, *mut c_char, *mut jvmtiParamInfo, jint);
+
+    /// Checks that none of the counts in the description is negative
+    fn check_counts(&self) -> Result<(), jvmtiError>;
+
+    /// Deallocates the allocations of the description other than the id, description and
+    /// parameters
+    ///
+    /// # Safety
+    ///
+    /// `env` must be the environment that returned the description.
+    unsafe fn deallocate_rest(&self, env: *mut jvmtiEnv);
+}
+
+impl ExtensionInfo for jvmtiExtensionFunctionInfo {
+    unsafe fn get_all(env: *mut jvmtiEnv, count: *mut jint, infos: *mut *mut Self) -> jvmtiError {
+        ((**env).v1.GetExtensionFunctions)(env, count, infos)
+    }
+
+    fn parts(&self) -> (*mut c_char, *mut c_char, *mut jvmtiParamInfo, jint) {
+        (
+            self.id,
+            self.short_description,
+            self.params,
+            self.param_count,
+        )
+    }
+
+    fn check_counts(&self) -> Result<(), jvmtiError> {
+        checked_len(self.param_count)?;
+        checked_len(self.error_count)?;
+        Ok(())
+    }
+
+    unsafe fn deallocate_rest(&self, env: *mut jvmtiEnv) {
+        deallocate(env, self.errors);
+    }
+}
+
+impl ExtensionInfo for jvmtiExtensionEventInfo {
+    unsafe fn get_all(env: *mut jvmtiEnv, count: *mut jint, infos: *mut *mut Self) -> jvmtiError {
+        ((**env).v1.GetExtensionEvents)(env, count, infos)
+    }
+
+    fn parts(&self) -> (*mut c_char, *mut c_char, *mut jvmtiParamInfo, jint) {
+        (
+            self.id,
+            self.short_description,
+            self.params,
+            self.param_count,
+        )
+    }
+
+    fn check_counts(&self) -> Result<(), jvmtiError> {
+        checked_len(self.param_count).map(drop)
+    }
+
+    unsafe fn deallocate_rest(&self, _env: *mut jvmtiEnv) {}
+}
+
+/// The descriptions returned by `GetExtensionFunctions` or `GetExtensionEvents`
+///
+/// They are deallocated, with every string and array in them, on drop.
+pub struct Extensions<I: ExtensionInfo> {
+    env: *mut jvmtiEnv,
+    infos: *mut I,
+    len: usize,
+}
+
+/// The extension functions of an environment
+pub type ExtensionFunctions = Extensions<jvmtiExtensionFunctionInfo>;
+
+/// The extension events of an environment
+pub type ExtensionEvents = Extensions<jvmtiExtensionEventInfo>;
+
+impl<I: ExtensionInfo> Extensions<I> {
+    /// Gets the extension functions or events of `env`
+    ///
+    /// A negative count in the descriptions is reported as `JVMTI_ERROR_INTERNAL`.
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid JVMTI environment that outlives the descriptions.
+    pub unsafe fn get(env: *mut jvmtiEnv) -> Result<Self, jvmtiError> {
+        let mut len = 0;
+        let mut infos = ptr::null_mut();
+        I::get_all(env, &mut len, &mut infos).check()?;
+        let len = match checked_len(len) {
+            Ok(len) => len,
+            Err(err) => {
+                deallocate(env, infos);
+                return Err(err);
+            }
+        };
+        let extensions = Self { env, infos, len };
+        for info in extensions.as_slice() {
+            // Dropping the descriptions skips the arrays with negative counts
+            info.check_counts()?;
+        }
+        Ok(extensions)
+    }
+
+    /// Returns the descriptions
+    pub fn as_slice(&self) -> &[I] {
+        if self.infos.is_null() {
+            &[]
+        } else {
+            // SAFETY: allocated by `get`
+            unsafe { slice::from_raw_parts(self.infos, self.len) }
+        }
+    }
+
+    /// Iterates over the descriptions
+    pub fn iter(&self) -> impl Iterator<Item = Extension<'_, I>> {
+        self.as_slice().iter().map(|info| Extension { info })
+    }
+
+    /// Returns the description with `id`, if any
+    pub fn find(&self, id: &str) -> Option<Extension<'_, I>> {
+        self.iter()
+            .find(|extension| extension.id().to_bytes() == id.as_bytes())
+    }
+}
+
+impl<I: ExtensionInfo> Drop for Extensions<I> {
+    fn drop(&mut self) {
+        // SAFETY: every pointer was allocated by `get`
+        unsafe {
+            for info in self.as_slice() {
+                let (id, short_description, params, _) = info.parts();
+                for param in (Extension { info }).params() {
+                    deallocate(self.env, param.name);
+                }
+                deallocate(self.env, id);
+                deallocate(self.env, short_description);
+                deallocate(self.env, params);
+                info.deallocate_rest(self.env);
+            }
+            deallocate(self.env, self.infos);
+        }
+    }
+}
+
+impl<I: ExtensionInfo> fmt::Debug for Extensions<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// The description of an extension function or event, borrowed from [`Extensions`]
+#[derive(Clone, Copy)]
+pub struct Extension<'a, I: ExtensionInfo> {
+    info: &'a I,
+}
+
+impl<'a, I: ExtensionInfo> Extension<'a, I> {
+    /// Returns the raw description
+    pub fn info(&self) -> &'a I {
+        self.info
+    }
+
+    /// Returns the id of the extension, like `com.sun.hotspot.functions.IsClassUnloadingEnabled`
+    pub fn id(&self) -> &'a CStr {
+        // SAFETY: a string allocated by the VM
+        unsafe { c_str(self.info.parts().0) }
+    }
+
+    /// Returns the short description of the extension
+    pub fn short_description(&self) -> &'a CStr {
+        // SAFETY: a string allocated by the VM
+        unsafe { c_str(self.info.parts().1) }
+    }
+
+    /// Returns the parameters, not counting the leading `jvmtiEnv`
+    pub fn params(&self) -> &'a [jvmtiParamInfo] {
+        let (_, _, params, param_count) = self.info.parts();
+        match checked_len(param_count) {
+            // SAFETY: an array allocated by the VM
+            Ok(len) if !params.is_null() => unsafe { slice::from_raw_parts(params, len) },
+            _ => &[],
+        }
+    }
+
+    /// Returns the name of the parameter at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn param_name(&self, index: usize) -> &'a CStr {
+        // SAFETY: a string allocated by the VM
+        unsafe { c_str(self.params()[index].name) }
+    }
+
+    /// Checks that the parameters have the `expected` kinds and types
+    ///
+    /// Whether a parameter may be null is not checked.
+    pub fn check_params(&self, expected: &[ParamSignature]) -> Result<(), SignatureError> {
+        let params = self.params();
+        if params.len() != expected.len() {
+            return Err(SignatureError::ParamCount {
+                expected: expected.len(),
+                found: params.len(),
+            });
+        }
+        for (index, (param, &expected)) in params.iter().zip(expected).enumerate() {
+            let found = ParamSignature::from(param);
+            if found != expected {
+                return Err(SignatureError::Param {
+                    index,
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Extension<'a, jvmtiExtensionFunctionInfo> {
+    /// Returns the errors the function may return, besides the universal errors
+    pub fn errors(&self) -> &'a [jvmtiError] {
+        let errors = self.info.errors;
+        match checked_len(self.info.error_count) {
+            // SAFETY: an array allocated by the VM
+            Ok(len) if !errors.is_null() => unsafe { slice::from_raw_parts(errors, len) },
+            _ => &[],
+        }
+    }
+
+    /// Returns the function typed by its arguments `A`, after checking that its parameters
+    /// have the `expected` kinds and types
+    ///
+    /// # Safety
+    ///
+    /// Each argument type in `A` must be how the parameter of the same index in `expected` is
+    /// passed, such as `*mut jboolean` for a `JVMTI_KIND_OUT` parameter of type
+    /// `JVMTI_TYPE_JBOOLEAN`.
+    pub unsafe fn typed<A: ExtensionArgs>(
+        &self,
+        expected: &[ParamSignature],
+    ) -> Result<ExtensionFn<A>, SignatureError> {
+        if A::COUNT != expected.len() {
+            return Err(SignatureError::ArgCount {
+                expected: expected.len(),
+                found: A::COUNT,
+            });
+        }
+        self.check_params(expected)?;
+        let func = self.info.func.ok_or(SignatureError::NullFunction)?;
+        Ok(ExtensionFn {
+            func,
+            args: PhantomData,
+        })
+    }
+}
+
+impl<I: ExtensionInfo> fmt::Debug for Extension<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extension")
+            .field("id", &self.id())
+            .field("short_description", &self.short_description())
+            .field("params", &self.params())
+            .finish()
+    }
+}
+
+unsafe fn c_str<'a>(ptr: *const c_char) -> &'a CStr {
+    if ptr.is_null() {
+        Default::default()
+    } else {
+        CStr::from_ptr(ptr)
+    }
+}
+
+/// The raw function pointer of an extension function
+///
+/// Extension functions are declared as variadic in C, and HotSpot implements them that way.
+pub type RawExtensionFn = unsafe extern "C" fn(jvmti_env: *mut jvmtiEnv, ...) -> jvmtiError;
+
+/// An extension function whose parameters have been checked, called with the arguments `A`
+pub struct ExtensionFn<A> {
+    func: RawExtensionFn,
+    args: PhantomData<fn(A)>,
+}
+
+impl<A: ExtensionArgs> ExtensionFn<A> {
+    /// Calls the function with `args`
+    ///
+    /// # Safety
+    ///
+    /// `env` must be the environment (or another environment of the same VM) that described
+    /// the function, and `args` must be valid for the function.
+    pub unsafe fn call(&self, env: *mut jvmtiEnv, args: A) -> Result<(), jvmtiError> {
+        args.call(self.func, env).check()
+    }
+
+    /// Returns the raw function pointer
+    pub fn raw(&self) -> RawExtensionFn {
+        self.func
+    }
+}
+
+impl<A> Clone for ExtensionFn<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A> Copy for ExtensionFn<A> {}
+
+impl<A> fmt::Debug for ExtensionFn<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtensionFn").field(&self.func).finish()
+    }
+}
+
+/// A type that is passed unchanged as a C variadic argument
+///
+/// # Safety
+///
+/// The type must have the same representation as the C type it's passed as, after C's default
+/// argument promotions. Types smaller than `int`, such as `jboolean`, are promoted and so don't
+/// qualify.
+pub unsafe trait ExtensionArg: Copy {}
+
+unsafe impl ExtensionArg for jint {}
+unsafe impl ExtensionArg for jlong {}
+unsafe impl ExtensionArg for jdouble {}
+unsafe impl<T> ExtensionArg for *mut T {}
+unsafe impl<T> ExtensionArg for *const T {}
+
+/// The arguments of an extension function, as a tuple of [`ExtensionArg`]s
+pub trait ExtensionArgs {
+    /// The number of arguments
+    const COUNT: usize;
+
+    /// Calls `func` with `env` and the arguments
+    ///
+    /// # Safety
+    ///
+    /// The arguments must be valid for `func`.
+    unsafe fn call(self, func: RawExtensionFn, env: *mut jvmtiEnv) -> jvmtiError;
+}
+
+macro_rules! extension_args {
+    ($(($($arg:ident),*),)*) => {
+        $(
+        impl<$($arg: ExtensionArg),*> ExtensionArgs for ($($arg,)*) {
+            const COUNT: usize = <[&str]>::len(&[$(stringify!($arg)),*]);
+
+            #[allow(non_snake_case)]
+            unsafe fn call(self, func: RawExtensionFn, env: *mut jvmtiEnv) -> jvmtiError {
+                let ($($arg,)*) = self;
+                func(env, $($arg),*)
+            }
+        }
+        )*
+    };
+}
+
+extension_args! {
+    (),
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+}
@@ -0,0 +1,150 @@
+//! Safe access to the [`jvmtiHeapReferenceInfo`] passed to heap callbacks
+//!
+//! `FollowReferences` passes the kind of each reference with a pointer to a
+//! `jvmtiHeapReferenceInfo` union, whose active member depends on the kind. [`HeapReference`]
+//! pairs the two once, so the details can then be read without `unsafe`.
+
+use super::*;
+
+/// A heap reference kind with the details of the reference
+#[derive(Clone, Copy, Debug)]
+pub enum HeapReference<'a> {
+    /// `JVMTI_HEAP_REFERENCE_FIELD`, a reference from an object to the value of one of its
+    /// instance fields
+    Field(&'a jvmtiHeapReferenceInfoField),
+    /// `JVMTI_HEAP_REFERENCE_STATIC_FIELD`, a reference from a class to the value of one of its
+    /// static fields
+    StaticField(&'a jvmtiHeapReferenceInfoField),
+    /// `JVMTI_HEAP_REFERENCE_ARRAY_ELEMENT`, a reference from an array to one of its elements
+    ArrayElement(&'a jvmtiHeapReferenceInfoArray),
+    /// `JVMTI_HEAP_REFERENCE_CONSTANT_POOL`, a reference from a class to a resolved entry in its
+    /// constant pool
+    ConstantPool(&'a jvmtiHeapReferenceInfoConstantPool),
+    /// `JVMTI_HEAP_REFERENCE_STACK_LOCAL`, a heap root reference from a thread's stack
+    StackLocal(&'a jvmtiHeapReferenceInfoStackLocal),
+    /// `JVMTI_HEAP_REFERENCE_JNI_LOCAL`, a heap root reference from a JNI local reference
+    JniLocal(&'a jvmtiHeapReferenceInfoJniLocal),
+    /// Any other kind, which has no details
+    Other(jvmtiHeapReferenceKind),
+}
+
+impl<'a> HeapReference<'a> {
+    /// Returns the reference of `kind` with the details in `info`
+    ///
+    /// # Safety
+    ///
+    /// `kind` and `info` must be the `reference_kind` and `reference_info` (or `kind` and
+    /// `info`) arguments of the same call to a heap reference or primitive field callback, and
+    /// the result must not be used after the callback returns.
+    pub unsafe fn new(kind: jvmtiHeapReferenceKind, info: *const jvmtiHeapReferenceInfo) -> Self {
+        if info.is_null() {
+            return Self::Other(kind);
+        }
+        let info = &*info;
+        match kind {
+            JVMTI_HEAP_REFERENCE_FIELD => Self::Field(&info.field),
+            JVMTI_HEAP_REFERENCE_STATIC_FIELD => Self::StaticField(&info.field),
+            JVMTI_HEAP_REFERENCE_ARRAY_ELEMENT => Self::ArrayElement(&info.array),
+            JVMTI_HEAP_REFERENCE_CONSTANT_POOL => Self::ConstantPool(&info.constant_pool),
+            JVMTI_HEAP_REFERENCE_STACK_LOCAL => Self::StackLocal(&info.stack_local),
+            JVMTI_HEAP_REFERENCE_JNI_LOCAL => Self::JniLocal(&info.jni_local),
+            kind => Self::Other(kind),
+        }
+    }
+
+    /// Returns the kind of the reference
+    pub fn kind(&self) -> jvmtiHeapReferenceKind {
+        match self {
+            Self::Field(_) => JVMTI_HEAP_REFERENCE_FIELD,
+            Self::StaticField(_) => JVMTI_HEAP_REFERENCE_STATIC_FIELD,
+            Self::ArrayElement(_) => JVMTI_HEAP_REFERENCE_ARRAY_ELEMENT,
+            Self::ConstantPool(_) => JVMTI_HEAP_REFERENCE_CONSTANT_POOL,
+            Self::StackLocal(_) => JVMTI_HEAP_REFERENCE_STACK_LOCAL,
+            Self::JniLocal(_) => JVMTI_HEAP_REFERENCE_JNI_LOCAL,
+            Self::Other(kind) => *kind,
+        }
+    }
+
+    /// Returns the index of the field, array element or constant pool entry, if the reference
+    /// has one
+    ///
+    /// Field indices are numbered as described for `jvmtiHeapReferenceInfoField`.
+    pub fn index(&self) -> Option<jint> {
+        match self {
+            Self::Field(info) | Self::StaticField(info) => Some(info.index),
+            Self::ArrayElement(info) => Some(info.index),
+            Self::ConstantPool(info) => Some(info.index),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the reference is a heap root: a stack local, JNI local or global,
+    /// system class, monitor, thread or other root
+    pub fn is_root(&self) -> bool {
+        matches!(
+            self.kind(),
+            JVMTI_HEAP_REFERENCE_JNI_GLOBAL
+                | JVMTI_HEAP_REFERENCE_SYSTEM_CLASS
+                | JVMTI_HEAP_REFERENCE_MONITOR
+                | JVMTI_HEAP_REFERENCE_STACK_LOCAL
+                | JVMTI_HEAP_REFERENCE_JNI_LOCAL
+                | JVMTI_HEAP_REFERENCE_THREAD
+                | JVMTI_HEAP_REFERENCE_OTHER
+        )
+    }
+}
+
+/// Returns the name of a heap reference kind's constant, like `JVMTI_HEAP_REFERENCE_FIELD`, or
+/// `None` if the kind is unknown
+pub fn heap_reference_kind_name(kind: jvmtiHeapReferenceKind) -> Option<&'static str> {
+    Some(match kind {
+        JVMTI_HEAP_REFERENCE_CLASS => "JVMTI_HEAP_REFERENCE_CLASS",
+        JVMTI_HEAP_REFERENCE_FIELD => "JVMTI_HEAP_REFERENCE_FIELD",
+        JVMTI_HEAP_REFERENCE_ARRAY_ELEMENT => "JVMTI_HEAP_REFERENCE_ARRAY_ELEMENT",
+        JVMTI_HEAP_REFERENCE_CLASS_LOADER => "JVMTI_HEAP_REFERENCE_CLASS_LOADER",
+        JVMTI_HEAP_REFERENCE_SIGNERS => "JVMTI_HEAP_REFERENCE_SIGNERS",
+        JVMTI_HEAP_REFERENCE_PROTECTION_DOMAIN => "JVMTI_HEAP_REFERENCE_PROTECTION_DOMAIN",
+        JVMTI_HEAP_REFERENCE_INTERFACE => "JVMTI_HEAP_REFERENCE_INTERFACE",
+        JVMTI_HEAP_REFERENCE_STATIC_FIELD => "JVMTI_HEAP_REFERENCE_STATIC_FIELD",
+        JVMTI_HEAP_REFERENCE_CONSTANT_POOL => "JVMTI_HEAP_REFERENCE_CONSTANT_POOL",
+        JVMTI_HEAP_REFERENCE_SUPERCLASS => "JVMTI_HEAP_REFERENCE_SUPERCLASS",
+        JVMTI_HEAP_REFERENCE_JNI_GLOBAL => "JVMTI_HEAP_REFERENCE_JNI_GLOBAL",
+        JVMTI_HEAP_REFERENCE_SYSTEM_CLASS => "JVMTI_HEAP_REFERENCE_SYSTEM_CLASS",
+        JVMTI_HEAP_REFERENCE_MONITOR => "JVMTI_HEAP_REFERENCE_MONITOR",
+        JVMTI_HEAP_REFERENCE_STACK_LOCAL => "JVMTI_HEAP_REFERENCE_STACK_LOCAL",
+        JVMTI_HEAP_REFERENCE_JNI_LOCAL => "JVMTI_HEAP_REFERENCE_JNI_LOCAL",
+        JVMTI_HEAP_REFERENCE_THREAD => "JVMTI_HEAP_REFERENCE_THREAD",
+        JVMTI_HEAP_REFERENCE_OTHER => "JVMTI_HEAP_REFERENCE_OTHER",
+        _ => return None,
+    })
+}
+
+impl jvmtiHeapCallbacks {
+    /// Returns callbacks with none set
+    pub const fn new() -> Self {
+        Self {
+            heap_iteration_callback: None,
+            heap_reference_callback: None,
+            primitive_field_callback: None,
+            array_primitive_value_callback: None,
+            string_primitive_value_callback: None,
+            reserved5: None,
+            reserved6: None,
+            reserved7: None,
+            reserved8: None,
+            reserved9: None,
+            reserved10: None,
+            reserved11: None,
+            reserved12: None,
+            reserved13: None,
+            reserved14: None,
+            reserved15: None,
+        }
+    }
+}
+
+impl Default for jvmtiHeapCallbacks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
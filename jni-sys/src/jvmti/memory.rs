@@ -0,0 +1,186 @@
+//! Ownership of memory allocated by JVMTI functions
+//!
+//! Functions such as `GetAllStackTraces`, `GetAllThreads` and `GetOwnedMonitorStackDepthInfo`
+//! return arrays allocated by the VM, and `GetThreadInfo` returns its thread name the same way.
+//! These must be returned with `Deallocate`, which [`JvmtiBuffer`] does when it's dropped.
+
+use core::ffi::{c_char, CStr};
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr;
+use core::slice;
+
+use super::{jvmtiEnv, jvmtiError, jvmtiFrameInfo, jvmtiStackInfo, JVMTI_ERROR_INTERNAL};
+use crate::jint;
+
+/// An array allocated by a JVMTI function, which is deallocated with `Deallocate` on drop
+///
+/// ```no_run
+/// use jni_sys::jvmti::memory::JvmtiBuffer;
+/// use jni_sys::jvmti::{jvmtiEnv, jvmtiError};
+///
+/// unsafe fn print_stack_depths(jvmti: *mut jvmtiEnv) -> Result<(), jvmtiError> {
+///     let stacks = JvmtiBuffer::from_call(jvmti, |count, stacks| {
+///         ((**jvmti).v1.GetAllStackTraces)(jvmti, 16, stacks, count)
+///     })?;
+///     for (i, stack) in stacks.iter().enumerate() {
+///         println!("{:?}: {} frames", stack.thread, stacks.frames(i).len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct JvmtiBuffer<T> {
+    env: *mut jvmtiEnv,
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T> JvmtiBuffer<T> {
+    /// Takes ownership of `len` elements at `ptr`, which were allocated by `env`
+    ///
+    /// `ptr` may be null if `len` is 0.
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid JVMTI environment that outlives the buffer, and `ptr` must be
+    /// null or an allocation from it that isn't deallocated elsewhere, and holds `len`
+    /// initialized elements.
+    pub unsafe fn from_raw_parts(env: *mut jvmtiEnv, ptr: *mut T, len: usize) -> Self {
+        Self { env, ptr, len }
+    }
+
+    /// Calls a JVMTI function that returns an array through a count pointer and an array
+    /// pointer, and takes ownership of the array
+    ///
+    /// A negative count is reported as `JVMTI_ERROR_INTERNAL`, after deallocating the array.
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid JVMTI environment that outlives the buffer, and `call` must
+    /// return an array allocated by it as the function does.
+    pub unsafe fn from_call(
+        env: *mut jvmtiEnv,
+        call: impl FnOnce(*mut jint, *mut *mut T) -> jvmtiError,
+    ) -> Result<Self, jvmtiError> {
+        let mut len = 0;
+        let mut ptr = ptr::null_mut();
+        call(&mut len, &mut ptr).check()?;
+        match checked_len(len) {
+            Ok(len) => Ok(Self::from_raw_parts(env, ptr, len)),
+            Err(err) => {
+                deallocate(env, ptr);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the elements
+    pub fn as_slice(&self) -> &[T] {
+        if self.ptr.is_null() {
+            &[]
+        } else {
+            // SAFETY: checked by the constructors
+            unsafe { slice::from_raw_parts(self.ptr, self.len) }
+        }
+    }
+
+    /// Returns the environment that allocated the buffer
+    pub fn env(&self) -> *mut jvmtiEnv {
+        self.env
+    }
+
+    /// Gives up ownership of the buffer, returning its pointer and length
+    ///
+    /// The caller is then responsible for deallocating the pointer.
+    pub fn into_raw_parts(self) -> (*mut T, usize) {
+        let buffer = ManuallyDrop::new(self);
+        (buffer.ptr, buffer.len)
+    }
+}
+
+impl JvmtiBuffer<c_char> {
+    /// Takes ownership of a C string allocated by `env`, such as the `name` of a
+    /// `jvmtiThreadInfo`
+    ///
+    /// The buffer holds the string with its nul terminator.
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid JVMTI environment that outlives the buffer, and `ptr` must be
+    /// null or a nul terminated string allocated by it that isn't deallocated elsewhere.
+    pub unsafe fn from_c_string(env: *mut jvmtiEnv, ptr: *mut c_char) -> Self {
+        let len = if ptr.is_null() {
+            0
+        } else {
+            CStr::from_ptr(ptr).to_bytes_with_nul().len()
+        };
+        Self::from_raw_parts(env, ptr, len)
+    }
+
+    /// Returns the string up to the first nul, or `None` if the buffer has no nul
+    pub fn to_c_str(&self) -> Option<&CStr> {
+        // SAFETY: `c_char` and `u8` have the same layout
+        let bytes = unsafe { slice::from_raw_parts(self.as_ptr().cast::<u8>(), self.len()) };
+        CStr::from_bytes_until_nul(bytes).ok()
+    }
+}
+
+impl JvmtiBuffer<jvmtiStackInfo> {
+    /// Returns the frames of the stack at `index`
+    ///
+    /// `GetAllStackTraces` and `GetThreadListStackTraces` allocate the frames in the same block
+    /// as the stacks, so they're deallocated with the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn frames(&self, index: usize) -> &[jvmtiFrameInfo] {
+        let stack = &self[index];
+        match checked_len(stack.frame_count) {
+            Ok(len) if !stack.frame_buffer.is_null() => {
+                // SAFETY: the frames are part of the buffer's allocation
+                unsafe { slice::from_raw_parts(stack.frame_buffer, len) }
+            }
+            _ => &[],
+        }
+    }
+}
+
+impl<T> Deref for JvmtiBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Drop for JvmtiBuffer<T> {
+    fn drop(&mut self) {
+        // SAFETY: checked by the constructors
+        unsafe { deallocate(self.env, self.ptr) }
+    }
+}
+
+/// Converts a count returned by a JVMTI function to a length, rejecting negative counts with
+/// `JVMTI_ERROR_INTERNAL`
+pub(super) fn checked_len(count: jint) -> Result<usize, jvmtiError> {
+    usize::try_from(count).map_err(|_| JVMTI_ERROR_INTERNAL)
+}
+
+/// Deallocates `ptr` with `Deallocate` unless it's null, ignoring errors
+///
+/// # Safety
+///
+/// `env` must be a valid JVMTI environment, and `ptr` must be null or an allocation from it.
+pub(super) unsafe fn deallocate<T>(env: *mut jvmtiEnv, ptr: *mut T) {
+    if !ptr.is_null() {
+        ((**env).v1.Deallocate)(env, ptr.cast());
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for JvmtiBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
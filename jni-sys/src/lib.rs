@@ -1,13 +1,34 @@
 #![doc(html_root_url = "https://docs.rs/jni-sys/0.4.1")]
 #![allow(non_snake_case, non_camel_case_types)]
 #![warn(rust_2018_idioms, missing_debug_implementations)]
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 use core::ffi::c_char;
 use core::ffi::c_void;
 
 use jni_sys_macros::jni_to_union;
 
+pub use jni_sys_macros::{jargs, jni_export};
+
+// Modules that have no counterpart in `jni.h` are hidden from the `systest` layout checks.
+#[cfg(not(ctest))]
+pub mod descriptor;
+#[cfg(not(ctest))]
+pub mod invocation;
+#[cfg(all(feature = "std", not(ctest)))]
+pub mod jdk;
+// Checked separately against `jvmti.h`
+#[cfg(not(ctest))]
+pub mod jvmti;
+#[cfg(not(ctest))]
+pub mod lifecycle;
+#[cfg(not(ctest))]
+pub mod mangle;
+#[cfg(all(feature = "std", not(ctest)))]
+pub mod options;
+#[cfg(not(ctest))]
+pub mod registry;
+
 // FIXME is this sufficiently correct?
 pub type va_list = *mut c_void;
 
@@ -87,6 +108,30 @@ impl core::fmt::Debug for jvalue {
     }
 }
 
+macro_rules! impl_jvalue_from {
+    ($($ty:ty => $member:ident,)*) => {
+        $(
+        impl From<$ty> for jvalue {
+            fn from($member: $ty) -> Self {
+                jvalue { $member }
+            }
+        }
+        )*
+    };
+}
+
+impl_jvalue_from! {
+    jboolean => z,
+    jbyte => b,
+    jchar => c,
+    jshort => s,
+    jint => i,
+    jlong => j,
+    jfloat => f,
+    jdouble => d,
+    jobject => l,
+}
+
 #[derive(Debug)]
 pub enum _jfieldID {}
 pub type jfieldID = *mut _jfieldID;
@@ -1402,6 +1447,7 @@ pub struct JNIInvokeInterface_ {
     ) -> jint,
 }
 
+#[cfg(not(feature = "dynamic"))]
 extern "system" {
     pub fn JNI_GetDefaultJavaVMInitArgs(args: *mut c_void) -> jint;
     pub fn JNI_CreateJavaVM(
@@ -0,0 +1,161 @@
+//! Library lifecycle entry points
+//!
+//! The JVM calls `JNI_OnLoad` when a native library is loaded with `System.loadLibrary`, and
+//! `JNI_OnUnload` when the class loader that loaded it is collected. A library linked statically
+//! into the VM (JDK 8 and later) exports `JNI_OnLoad_<libname>` and `JNI_OnUnload_<libname>`
+//! instead.
+//!
+//! [`jni_on_load!`](crate::jni_on_load) and [`jni_on_unload!`](crate::jni_on_unload) export
+//! a Rust function under these names with the right ABI, and keep the VM in the
+//! [`registry`](crate::registry).
+
+use core::ffi::c_void;
+
+use crate::invocation::JNI_VERSIONS;
+use crate::{jint, JavaVM, JNI_ERR, JNI_VERSION_1_8};
+
+/// The type of `JNI_OnLoad` and `JNI_OnLoad_<libname>`
+///
+/// Returns the JNI version needed by the library, or a negative status if loading failed.
+pub type JNI_OnLoad_fn = unsafe extern "system" fn(vm: *mut JavaVM, reserved: *mut c_void) -> jint;
+
+/// The type of `JNI_OnUnload` and `JNI_OnUnload_<libname>`
+pub type JNI_OnUnload_fn = unsafe extern "system" fn(vm: *mut JavaVM, reserved: *mut c_void);
+
+/// Returns `version` if it's one of the [`JNI_VERSIONS`] or a negative status, or else
+/// `JNI_ERR`
+///
+/// This checks the result of a `JNI_OnLoad` declared with [`jni_on_load!`](crate::jni_on_load).
+pub fn checked_on_load_version(version: jint) -> jint {
+    if version < 0 || JNI_VERSIONS.contains(&version) {
+        version
+    } else {
+        JNI_ERR
+    }
+}
+
+/// Like [`checked_on_load_version`], but also returns `JNI_ERR` for versions older than
+/// `JNI_VERSION_1_8`
+///
+/// The VM only accepts JNI 1.8 or later from `JNI_OnLoad_<libname>`.
+pub fn checked_static_on_load_version(version: jint) -> jint {
+    match checked_on_load_version(version) {
+        version if (0..JNI_VERSION_1_8).contains(&version) => JNI_ERR,
+        version => version,
+    }
+}
+
+/// Exports a function as `JNI_OnLoad`, or as `JNI_OnLoad_<libname>` if a library name is given
+///
+/// The function takes the VM and the reserved pointer, and returns the JNI version the library
+/// needs. A version that isn't one of the [`JNI_VERSIONS`](crate::invocation::JNI_VERSIONS)
+/// (or older than `JNI_VERSION_1_8` for a statically linked library) is replaced with
+/// `JNI_ERR`, which fails loading. Negative statuses are returned as they are.
+///
+/// If loading succeeds, the VM is registered with the version in the
+/// [`registry`](crate::registry).
+///
+/// ```
+/// use jni_sys::{jint, JavaVM, JNI_VERSION_1_8};
+/// use std::ffi::c_void;
+///
+/// fn on_load(_vm: *mut JavaVM, _reserved: *mut c_void) -> jint {
+///     JNI_VERSION_1_8
+/// }
+///
+/// jni_sys::jni_on_load!(on_load);
+/// jni_sys::jni_on_load!(on_load, "mylib");
+/// ```
+#[macro_export]
+macro_rules! jni_on_load {
+    ($on_load:path) => {
+        const _: () = {
+            #[no_mangle]
+            unsafe extern "system" fn JNI_OnLoad(
+                vm: *mut $crate::JavaVM,
+                reserved: *mut ::core::ffi::c_void,
+            ) -> $crate::jint {
+                let on_load: unsafe fn(
+                    *mut $crate::JavaVM,
+                    *mut ::core::ffi::c_void,
+                ) -> $crate::jint = $on_load;
+                let version = $crate::lifecycle::checked_on_load_version(on_load(vm, reserved));
+                if version >= 0 {
+                    $crate::registry::register_java_vm(vm, version);
+                }
+                version
+            }
+            const _: $crate::lifecycle::JNI_OnLoad_fn = JNI_OnLoad;
+        };
+    };
+    ($on_load:path, $libname:literal) => {
+        const _: () = {
+            #[export_name = concat!("JNI_OnLoad_", $libname)]
+            unsafe extern "system" fn on_load_static(
+                vm: *mut $crate::JavaVM,
+                reserved: *mut ::core::ffi::c_void,
+            ) -> $crate::jint {
+                let on_load: unsafe fn(
+                    *mut $crate::JavaVM,
+                    *mut ::core::ffi::c_void,
+                ) -> $crate::jint = $on_load;
+                let version =
+                    $crate::lifecycle::checked_static_on_load_version(on_load(vm, reserved));
+                if version >= 0 {
+                    $crate::registry::register_java_vm(vm, version);
+                }
+                version
+            }
+            const _: $crate::lifecycle::JNI_OnLoad_fn = on_load_static;
+        };
+    };
+}
+
+/// Exports a function as `JNI_OnUnload`, or as `JNI_OnUnload_<libname>` if a library name is
+/// given
+///
+/// The function takes the VM and the reserved pointer. The VM is then unregistered from the
+/// [`registry`](crate::registry).
+///
+/// ```
+/// use jni_sys::JavaVM;
+/// use std::ffi::c_void;
+///
+/// fn on_unload(_vm: *mut JavaVM, _reserved: *mut c_void) {}
+///
+/// jni_sys::jni_on_unload!(on_unload);
+/// jni_sys::jni_on_unload!(on_unload, "mylib");
+/// ```
+#[macro_export]
+macro_rules! jni_on_unload {
+    ($on_unload:path) => {
+        const _: () = {
+            #[no_mangle]
+            unsafe extern "system" fn JNI_OnUnload(
+                vm: *mut $crate::JavaVM,
+                reserved: *mut ::core::ffi::c_void,
+            ) {
+                let on_unload: unsafe fn(*mut $crate::JavaVM, *mut ::core::ffi::c_void) =
+                    $on_unload;
+                on_unload(vm, reserved);
+                $crate::registry::unregister_java_vm();
+            }
+            const _: $crate::lifecycle::JNI_OnUnload_fn = JNI_OnUnload;
+        };
+    };
+    ($on_unload:path, $libname:literal) => {
+        const _: () = {
+            #[export_name = concat!("JNI_OnUnload_", $libname)]
+            unsafe extern "system" fn on_unload_static(
+                vm: *mut $crate::JavaVM,
+                reserved: *mut ::core::ffi::c_void,
+            ) {
+                let on_unload: unsafe fn(*mut $crate::JavaVM, *mut ::core::ffi::c_void) =
+                    $on_unload;
+                on_unload(vm, reserved);
+                $crate::registry::unregister_java_vm();
+            }
+            const _: $crate::lifecycle::JNI_OnUnload_fn = on_unload_static;
+        };
+    };
+}
@@ -0,0 +1,374 @@
+//! Native method symbol names
+//!
+//! The JVM resolves a `native` method that wasn't registered with `RegisterNatives` by looking
+//! up an exported symbol named after the method. The short name is `Java_`, the mangled class
+//! name, `_` and the mangled method name. Overloaded methods use the long name, which appends
+//! `__` and the mangled parameter descriptors.
+//!
+//! Mangling replaces `/` (or `.`) with `_`, and escapes `_` as `_1`, `;` as `_2`, `[` as `_3` and
+//! anything other than an ASCII letter or digit as `_0xxxx`, one per UTF-16 code unit.
+
+use core::fmt;
+
+use crate::descriptor::MethodDescriptor;
+
+const PREFIX: &str = "Java_";
+
+/// The exported symbol name for a native method
+///
+/// The [`Display`](fmt::Display) implementation writes the mangled name, so the name can be
+/// formatted into any buffer without allocating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Symbol<'a> {
+    class: &'a str,
+    method: &'a str,
+    params: Option<&'a str>,
+}
+
+impl<'a> Symbol<'a> {
+    /// Returns the short symbol name for a method
+    ///
+    /// The class name may use either `/` or `.` to separate packages.
+    pub const fn short(class: &'a str, method: &'a str) -> Self {
+        Self {
+            class,
+            method,
+            params: None,
+        }
+    }
+
+    /// Returns the long symbol name for an overloaded method
+    pub const fn long(class: &'a str, method: &'a str, descriptor: &MethodDescriptor<'a>) -> Self {
+        Self {
+            class,
+            method,
+            params: Some(descriptor.params_descriptor()),
+        }
+    }
+
+    /// Returns the long symbol name if there is a descriptor, or else the short name
+    pub const fn new(
+        class: &'a str,
+        method: &'a str,
+        descriptor: Option<&MethodDescriptor<'a>>,
+    ) -> Self {
+        match descriptor {
+            Some(descriptor) => Self::long(class, method, descriptor),
+            None => Self::short(class, method),
+        }
+    }
+
+    /// Returns the short form of this symbol name
+    pub const fn to_short(&self) -> Self {
+        Self::short(self.class, self.method)
+    }
+
+    /// Returns whether this is a long symbol name
+    pub const fn is_long(&self) -> bool {
+        self.params.is_some()
+    }
+}
+
+impl fmt::Display for Symbol<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(PREFIX)?;
+        write_mangled(f, self.class)?;
+        f.write_str("_")?;
+        write_mangled(f, self.method)?;
+        if let Some(params) = self.params {
+            f.write_str("__")?;
+            write_mangled(f, params)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_mangled(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => write!(f, "{c}")?,
+            '/' | '.' => f.write_str("_")?,
+            '_' => f.write_str("_1")?,
+            ';' => f.write_str("_2")?,
+            '[' => f.write_str("_3")?,
+            _ => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(f, "_0{unit:04x}")?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A symbol name split into its class, method and (for long names) parameter parts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DemangledSymbol<'a> {
+    class: &'a str,
+    method: &'a str,
+    params: Option<&'a str>,
+}
+
+impl<'a> DemangledSymbol<'a> {
+    /// Returns the class name, with `/` separating packages
+    pub const fn class(&self) -> Unmangled<'a> {
+        Unmangled {
+            mangled: self.class,
+        }
+    }
+
+    /// Returns the method name
+    pub const fn method(&self) -> Unmangled<'a> {
+        Unmangled {
+            mangled: self.method,
+        }
+    }
+
+    /// Returns the parameter descriptors (without parentheses) if this is a long symbol name
+    pub const fn params(&self) -> Option<Unmangled<'a>> {
+        match self.params {
+            Some(mangled) => Some(Unmangled { mangled }),
+            None => None,
+        }
+    }
+}
+
+/// A demangled part of a symbol name
+///
+/// The part is decoded lazily, either as `char`s by iterating or by formatting it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Unmangled<'a> {
+    mangled: &'a str,
+}
+
+impl<'a> Unmangled<'a> {
+    /// Returns the part as it appears in the symbol name
+    pub const fn as_mangled(&self) -> &'a str {
+        self.mangled
+    }
+
+    /// Returns an iterator over the demangled characters
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        // Surrogate pairs were checked by `demangle`
+        char::decode_utf16(Units {
+            bytes: self.mangled.as_bytes(),
+        })
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+impl fmt::Display for Unmangled<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.chars() {
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq<str> for Unmangled<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl PartialEq<&str> for Unmangled<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+/// Iterator over the UTF-16 code units of an already validated mangled part
+struct Units<'a> {
+    bytes: &'a [u8],
+}
+
+impl Iterator for Units<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        let (unit, len) = match *self.bytes {
+            [] => return None,
+            [b'_', b'0', ..] => (parse_hex(&self.bytes[2..6])?, 6),
+            [b'_', b'1', ..] => (u16::from(b'_'), 2),
+            [b'_', b'2', ..] => (u16::from(b';'), 2),
+            [b'_', b'3', ..] => (u16::from(b'['), 2),
+            [b'_', ..] => (u16::from(b'/'), 1),
+            [b, ..] => (u16::from(b), 1),
+        };
+        self.bytes = &self.bytes[len..];
+        Some(unit)
+    }
+}
+
+fn parse_hex(digits: &[u8]) -> Option<u16> {
+    if digits.len() != 4 {
+        return None;
+    }
+    let mut unit = 0u16;
+    for digit in digits {
+        let value = (*digit as char).to_digit(16)?;
+        unit = (unit << 4) | value as u16;
+    }
+    Some(unit)
+}
+
+/// Why a symbol name failed to demangle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DemangleErrorKind {
+    /// The symbol doesn't start with `Java_`
+    MissingPrefix,
+    /// The symbol contains a character that can't appear in a mangled name
+    InvalidChar(char),
+    /// An `_0` escape isn't followed by four hex digits
+    InvalidEscape,
+    /// An `_0` escape encodes half of a surrogate pair without the other half
+    UnpairedSurrogate,
+    /// A class, package or method name is empty
+    EmptyName,
+    /// There is no `_` separating the class name from the method name
+    MissingMethod,
+}
+
+/// Error returned when a symbol name fails to demangle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DemangleError {
+    offset: usize,
+    kind: DemangleErrorKind,
+}
+
+impl DemangleError {
+    const fn new(offset: usize, kind: DemangleErrorKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// Returns the byte offset into the symbol name at which the error was found
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the reason the symbol name is invalid
+    pub const fn kind(&self) -> DemangleErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DemangleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DemangleErrorKind::MissingPrefix => f.write_str("symbol doesn't start with 'Java_'")?,
+            DemangleErrorKind::InvalidChar(c) => {
+                write!(f, "unexpected character '{}'", c.escape_default())?
+            }
+            DemangleErrorKind::InvalidEscape => f.write_str("invalid '_0' escape")?,
+            DemangleErrorKind::UnpairedSurrogate => f.write_str("unpaired surrogate")?,
+            DemangleErrorKind::EmptyName => f.write_str("empty name")?,
+            DemangleErrorKind::MissingMethod => f.write_str("missing method name")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+/// Splits a native method symbol name into its class, method and parameter parts
+pub fn demangle(symbol: &str) -> Result<DemangledSymbol<'_>, DemangleError> {
+    if !symbol.starts_with(PREFIX) {
+        return Err(DemangleError::new(0, DemangleErrorKind::MissingPrefix));
+    }
+    let bytes = symbol.as_bytes();
+    let mut pos = PREFIX.len();
+    let mut segment_start = pos;
+    // The last separator in the class and method part, which ends the class name
+    let mut class_end = None;
+    let mut params_start = None;
+    let mut pending_high_surrogate = None;
+
+    while pos < bytes.len() {
+        let unit = match bytes[pos..] {
+            [b'_', b'0', ..] => {
+                let unit = parse_hex(bytes.get(pos + 2..pos + 6).unwrap_or_default())
+                    .ok_or(DemangleError::new(pos, DemangleErrorKind::InvalidEscape))?;
+                Some((unit, 6))
+            }
+            [b'_', b'1' | b'2' | b'3', ..] => Some((0, 2)),
+            [b'_', ..] => None,
+            [b, ..] if b.is_ascii_alphanumeric() => Some((0, 1)),
+            _ => {
+                let c = symbol[pos..].chars().next().unwrap_or_default();
+                return Err(DemangleError::new(pos, DemangleErrorKind::InvalidChar(c)));
+            }
+        };
+
+        let Some((unit, len)) = unit else {
+            // An unescaped `_` separates names, and `__` starts the parameters, unless the
+            // second `_` starts an escape that can't begin a descriptor
+            if let Some(start) = pending_high_surrogate {
+                return Err(DemangleError::new(
+                    start,
+                    DemangleErrorKind::UnpairedSurrogate,
+                ));
+            }
+            if params_start.is_none() {
+                if pos == segment_start {
+                    return Err(DemangleError::new(pos, DemangleErrorKind::EmptyName));
+                }
+                let starts_params = match bytes[pos + 1..] {
+                    [b'_', b'0' | b'1' | b'2', ..] => false,
+                    [b'_', ..] => true,
+                    _ => false,
+                };
+                if starts_params {
+                    params_start = Some(pos + 2);
+                    pos += 2;
+                    segment_start = pos;
+                    continue;
+                }
+                class_end = Some(pos);
+            } else if pos == segment_start {
+                return Err(DemangleError::new(pos, DemangleErrorKind::EmptyName));
+            }
+            pos += 1;
+            segment_start = pos;
+            continue;
+        };
+
+        match (pending_high_surrogate, unit) {
+            (None, 0xd800..=0xdbff) => pending_high_surrogate = Some(pos),
+            (None, 0xdc00..=0xdfff) => {
+                return Err(DemangleError::new(
+                    pos,
+                    DemangleErrorKind::UnpairedSurrogate,
+                ))
+            }
+            (Some(_), 0xdc00..=0xdfff) => pending_high_surrogate = None,
+            (Some(start), _) => {
+                return Err(DemangleError::new(
+                    start,
+                    DemangleErrorKind::UnpairedSurrogate,
+                ))
+            }
+            (None, _) => {}
+        }
+        pos += len;
+    }
+
+    if let Some(start) = pending_high_surrogate {
+        return Err(DemangleError::new(
+            start,
+            DemangleErrorKind::UnpairedSurrogate,
+        ));
+    }
+    let names_end = params_start.map_or(bytes.len(), |start| start - 2);
+    if params_start.is_none() && segment_start == bytes.len() {
+        return Err(DemangleError::new(pos, DemangleErrorKind::EmptyName));
+    }
+    let class_end = class_end.ok_or(DemangleError::new(
+        names_end,
+        DemangleErrorKind::MissingMethod,
+    ))?;
+
+    Ok(DemangledSymbol {
+        class: &symbol[PREFIX.len()..class_end],
+        method: &symbol[class_end + 1..names_end],
+        params: params_start.map(|start| &symbol[start..]),
+    })
+}
@@ -0,0 +1,478 @@
+//! Parsing `java` command lines into Java VM options
+//!
+//! [`OptionsParser`] turns the arguments of a `java` command line into the option strings
+//! expected in `JavaVMInitArgs`, the way the JDK launcher does. It prepends `JDK_JAVA_OPTIONS`,
+//! expands `@argfiles`, converts `-cp` to `-Djava.class.path=` and joins the values of module
+//! options such as `--add-opens`. [`JavaVmOptions`] then holds the strings as `JavaVMOption`s.
+//!
+//! `JAVA_TOOL_OPTIONS` is read by the VM itself, so it must not be added to the options again.
+//! [`split_options`] can split it (or any other variable in the same format) for inspection.
+//!
+//! This module is only available with the `std` feature.
+
+use std::collections::VecDeque;
+use std::ffi::{c_void, CString, NulError};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::ptr;
+
+use crate::{jboolean, jint, JavaVMInitArgs, JavaVMOption};
+
+/// Options that take their value from the next argument, and the option the VM expects
+const VALUE_OPTIONS: &[(&str, &str)] = &[
+    ("-cp", "-Djava.class.path="),
+    ("-classpath", "-Djava.class.path="),
+    ("--class-path", "-Djava.class.path="),
+    ("-p", "--module-path="),
+    ("--module-path", "--module-path="),
+    ("--upgrade-module-path", "--upgrade-module-path="),
+    ("--add-modules", "--add-modules="),
+    ("--enable-native-access", "--enable-native-access="),
+    ("--limit-modules", "--limit-modules="),
+    ("--add-exports", "--add-exports="),
+    ("--add-opens", "--add-opens="),
+    ("--add-reads", "--add-reads="),
+    ("--patch-module", "--patch-module="),
+];
+
+/// Options handled by the launcher that have no VM equivalent
+const LAUNCHER_ONLY_OPTIONS: &[&str] = &[
+    "-m",
+    "--module",
+    "-h",
+    "-?",
+    "-help",
+    "--help",
+    "-X",
+    "--help-extra",
+    "-version",
+    "--version",
+    "-showversion",
+    "--show-version",
+    "-fullversion",
+    "--full-version",
+    "--dry-run",
+    "--list-modules",
+    "-d",
+    "--describe-module",
+    "--validate-modules",
+    "--source",
+];
+
+/// Error returned when a command line can't be parsed
+#[derive(Debug)]
+pub enum OptionsError {
+    /// A quote in the options isn't closed
+    UnmatchedQuote(String),
+    /// An option is missing its value
+    MissingValue(String),
+    /// An option (or main class) isn't allowed in `JDK_JAVA_OPTIONS`
+    NotAllowedInEnv(String),
+    /// An option is only understood by the `java` launcher
+    LauncherOnly(String),
+    /// An `@argfile` couldn't be read
+    Argfile {
+        /// The path of the argument file
+        path: PathBuf,
+        /// The error from reading it
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnmatchedQuote(options) => write!(f, "unmatched quote in `{options}`"),
+            Self::MissingValue(option) => write!(f, "`{option}` requires a value"),
+            Self::NotAllowedInEnv(option) => {
+                write!(f, "`{option}` is not allowed in JDK_JAVA_OPTIONS")
+            }
+            Self::LauncherOnly(option) => {
+                write!(f, "`{option}` is only supported by the java launcher")
+            }
+            Self::Argfile { path, source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Argfile { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Splits options separated by whitespace, as in `JDK_JAVA_OPTIONS` or `JAVA_TOOL_OPTIONS`
+///
+/// Text between single or double quotes is kept as it is, including whitespace, and the quotes
+/// are removed. There are no escape characters.
+pub fn split_options(options: &str) -> Result<Vec<String>, OptionsError> {
+    let mut args = vec![];
+    let mut chars = options.chars();
+    let mut arg: Option<String> = None;
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                let arg = arg.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some(close) if close == c => break,
+                        Some(quoted) => arg.push(quoted),
+                        None => return Err(OptionsError::UnmatchedQuote(options.to_owned())),
+                    }
+                }
+            }
+            c if c.is_whitespace() => args.extend(arg.take()),
+            c => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(arg);
+    Ok(args)
+}
+
+/// Splits the contents of an `@argfile` into arguments
+///
+/// Arguments are separated by whitespace or line breaks, and may be quoted with single or
+/// double quotes. Within quotes, `\` escapes the next character (`\n`, `\r`, `\t` and `\f`
+/// are control characters) and a `\` at the end of a line continues the argument after the
+/// next line's leading whitespace. A quote that isn't closed ends at the end of the line.
+///
+/// Outside of quotes, `#` comments out the rest of the line. As in the `java` launcher, it also
+/// discards the unquoted text of the argument it's in, so `-Dx=a#b` is dropped entirely, while
+/// quoted text before it is joined to the start of the next argument.
+pub fn split_argfile(contents: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut arg: Option<String> = None;
+    // The length of `arg` that a comment keeps, up to the end of its last quote
+    let mut quoted_len = 0;
+    // Quoted text of an argument cut short by a comment
+    let mut carried: Option<String> = None;
+    let mut quote = None;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (_, '\n' | '\r') => {
+                quote = None;
+                args.extend(arg.take());
+            }
+            (Some(_), '\\') => match chars.next() {
+                Some('\r' | '\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+                Some(escaped) => {
+                    let escaped = match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'f' => '\u{c}',
+                        escaped => escaped,
+                    };
+                    start_arg(&mut arg, &mut carried, &mut quoted_len).push(escaped);
+                }
+                None => {}
+            },
+            (Some(open), c) if c == open => {
+                quote = None;
+                quoted_len = arg.as_ref().map_or(0, String::len);
+            }
+            (Some(_), c) => start_arg(&mut arg, &mut carried, &mut quoted_len).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                quoted_len = start_arg(&mut arg, &mut carried, &mut quoted_len).len();
+            }
+            (None, ' ' | '\t' | '\u{c}') => args.extend(arg.take()),
+            (None, '#') => {
+                if let Some(mut partial) = arg.take() {
+                    partial.truncate(quoted_len);
+                    carried = Some(partial).filter(|partial| !partial.is_empty());
+                }
+                while chars.next_if(|&c| c != '\n' && c != '\r').is_some() {}
+            }
+            (None, c) => start_arg(&mut arg, &mut carried, &mut quoted_len).push(c),
+        }
+    }
+    args.extend(arg);
+    args
+}
+
+/// Returns the argument being split, starting it with any text carried over a comment
+fn start_arg<'a>(
+    arg: &'a mut Option<String>,
+    carried: &mut Option<String>,
+    quoted_len: &mut usize,
+) -> &'a mut String {
+    arg.get_or_insert_with(|| {
+        let arg = carried.take().unwrap_or_default();
+        *quoted_len = arg.len();
+        arg
+    })
+}
+
+/// What the `java` command line launches
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Launch {
+    /// A main class, named with `.` or `/` separating packages
+    MainClass(String),
+    /// The main class of a JAR file, given with `-jar`
+    Jar(String),
+}
+
+/// A parsed `java` command line
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParsedArgs {
+    /// The options to create the VM with
+    pub options: Vec<String>,
+    /// What to launch, if the command line names a main class or JAR file
+    pub launch: Option<Launch>,
+    /// The arguments after the main class or JAR file, which are passed to `main`
+    pub args: Vec<String>,
+}
+
+/// Parser for `java` command lines
+///
+/// The parser is configured explicitly, or from the process environment with
+/// [`OptionsParser::from_env`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionsParser {
+    jdk_java_options: Option<String>,
+    argfiles: bool,
+}
+
+impl Default for OptionsParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An argument waiting to be parsed
+struct Pending {
+    arg: String,
+    /// Whether the argument came from `JDK_JAVA_OPTIONS`
+    from_env: bool,
+    /// Whether the argument may be an `@argfile`
+    expand: bool,
+}
+
+impl OptionsParser {
+    /// Returns a parser that expands `@argfiles` and has no `JDK_JAVA_OPTIONS`
+    pub fn new() -> Self {
+        Self {
+            jdk_java_options: None,
+            argfiles: true,
+        }
+    }
+
+    /// Returns a parser with the `JDK_JAVA_OPTIONS` of the process environment
+    pub fn from_env() -> Self {
+        let mut parser = Self::new();
+        if let Some(options) = std::env::var_os("JDK_JAVA_OPTIONS") {
+            parser = parser.jdk_java_options(options.to_string_lossy());
+        }
+        parser
+    }
+
+    /// Sets the `JDK_JAVA_OPTIONS` to prepend to the command line
+    ///
+    /// They're split with [`split_options`] and may not name a main class, `-jar` or an option
+    /// that would only be understood by the launcher.
+    pub fn jdk_java_options(mut self, options: impl Into<String>) -> Self {
+        self.jdk_java_options = Some(options.into());
+        self
+    }
+
+    /// Sets whether arguments starting with `@` are expanded from argument files
+    ///
+    /// When set, which is the default, `@@` escapes a leading `@`. Expansion stops at
+    /// `--disable-@files` and at the main class or JAR file.
+    pub fn expand_argfiles(mut self, expand: bool) -> Self {
+        self.argfiles = expand;
+        self
+    }
+
+    /// Parses the arguments of a `java` command line, without the program name
+    ///
+    /// Arguments starting with `-` are options for the VM, except for those translated for
+    /// it: the class path options (`-cp`, `-classpath` and `--class-path`) become
+    /// `-Djava.class.path=`, and module options given as two arguments, such as
+    /// `--add-opens java.base/java.lang=ALL-UNNAMED`, are joined with `=`. `-jar` sets the
+    /// class path to the JAR file. The first other argument is the main class, and the rest
+    /// are passed to `main`.
+    pub fn parse<I>(&self, args: I) -> Result<ParsedArgs, OptionsError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut pending = VecDeque::new();
+        if let Some(options) = &self.jdk_java_options {
+            for arg in split_options(options)? {
+                pending.push_back(Pending {
+                    arg,
+                    from_env: true,
+                    expand: true,
+                });
+            }
+        }
+        for arg in args {
+            pending.push_back(Pending {
+                arg: arg.into(),
+                from_env: false,
+                expand: true,
+            });
+        }
+
+        let mut parsed = ParsedArgs::default();
+        let mut argfiles = self.argfiles;
+        loop {
+            if parsed.launch.is_some() {
+                parsed
+                    .args
+                    .extend(pending.drain(..).map(|pending| pending.arg));
+                break;
+            }
+            if argfiles {
+                expand_argfiles(&mut pending)?;
+            }
+            let Some(Pending { arg, from_env, .. }) = pending.pop_front() else {
+                break;
+            };
+            if arg == "--disable-@files" {
+                argfiles = false;
+                continue;
+            }
+
+            let mut value = |option: &str| -> Result<String, OptionsError> {
+                if argfiles {
+                    expand_argfiles(&mut pending)?;
+                }
+                let value = pending
+                    .pop_front()
+                    .ok_or_else(|| OptionsError::MissingValue(option.to_owned()))?;
+                Ok(value.arg)
+            };
+
+            if let Some((_, vm_option)) = VALUE_OPTIONS.iter().find(|(option, _)| *option == arg) {
+                parsed.options.push(format!("{vm_option}{}", value(&arg)?));
+            } else if let Some(class_path) = arg.strip_prefix("--class-path=") {
+                parsed
+                    .options
+                    .push(format!("-Djava.class.path={class_path}"));
+            } else if arg == "-jar" {
+                if from_env {
+                    return Err(OptionsError::NotAllowedInEnv(arg));
+                }
+                let jar = value(&arg)?;
+                parsed.options.push(format!("-Djava.class.path={jar}"));
+                parsed.launch = Some(Launch::Jar(jar));
+            } else if is_launcher_only(&arg) {
+                return Err(if from_env {
+                    OptionsError::NotAllowedInEnv(arg)
+                } else {
+                    OptionsError::LauncherOnly(arg)
+                });
+            } else if arg.starts_with('-') {
+                parsed.options.push(arg);
+            } else if from_env {
+                return Err(OptionsError::NotAllowedInEnv(arg));
+            } else {
+                parsed.launch = Some(Launch::MainClass(arg));
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Expands the `@argfiles` at the front of `pending` until it starts with another argument
+fn expand_argfiles(pending: &mut VecDeque<Pending>) -> Result<(), OptionsError> {
+    while let Some(front) = pending.front_mut().filter(|front| front.expand) {
+        if let Some(escaped) = front.arg.strip_prefix("@@") {
+            front.arg = format!("@{escaped}");
+            front.expand = false;
+            continue;
+        }
+        let Some(path) = front.arg.strip_prefix('@') else {
+            front.expand = false;
+            continue;
+        };
+        let contents = fs::read_to_string(path).map_err(|source| OptionsError::Argfile {
+            path: PathBuf::from(path),
+            source,
+        })?;
+        let from_env = front.from_env;
+        pending.pop_front();
+        // Argument files aren't expanded recursively
+        for arg in split_argfile(&contents).into_iter().rev() {
+            pending.push_front(Pending {
+                arg,
+                from_env,
+                expand: false,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn is_launcher_only(arg: &str) -> bool {
+    LAUNCHER_ONLY_OPTIONS.iter().any(|option| {
+        arg == *option
+            || (option.starts_with("--")
+                && arg
+                    .strip_prefix(option)
+                    .is_some_and(|rest| rest.starts_with('=')))
+    })
+}
+
+/// Options for `JavaVMInitArgs`, owning their strings
+#[derive(Debug)]
+pub struct JavaVmOptions {
+    // The options point into the strings, which don't move when the vector does
+    strings: Vec<CString>,
+    options: Vec<JavaVMOption>,
+}
+
+impl JavaVmOptions {
+    /// Returns options for the given strings
+    pub fn new<I>(options: I) -> Result<Self, NulError>
+    where
+        I: IntoIterator,
+        I::Item: Into<Vec<u8>>,
+    {
+        let strings = options
+            .into_iter()
+            .map(CString::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let options = strings
+            .iter()
+            .map(|string| JavaVMOption {
+                optionString: string.as_ptr().cast_mut(),
+                extraInfo: ptr::null_mut::<c_void>(),
+            })
+            .collect();
+        Ok(Self { strings, options })
+    }
+
+    /// Returns the option strings
+    pub fn strings(&self) -> &[CString] {
+        &self.strings
+    }
+
+    /// Returns the options
+    pub fn as_slice(&self) -> &[JavaVMOption] {
+        &self.options
+    }
+
+    /// Returns arguments for `JNI_CreateJavaVM` that point at these options
+    ///
+    /// The arguments must not be used after `self` is dropped.
+    pub fn init_args(&mut self, version: jint, ignore_unrecognized: jboolean) -> JavaVMInitArgs {
+        JavaVMInitArgs {
+            version,
+            nOptions: jint::try_from(self.options.len()).unwrap_or(jint::MAX),
+            options: self.options.as_mut_ptr(),
+            ignoreUnrecognized: ignore_unrecognized,
+        }
+    }
+}
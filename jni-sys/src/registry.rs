@@ -0,0 +1,119 @@
+//! A global registry of the process's Java VM
+//!
+//! The `*mut JavaVM` is only handed to `JNI_OnLoad`, or found later with `GetJavaVM` or
+//! `JNI_GetCreatedJavaVMs`. Registering it here makes it available to native code on any
+//! thread. The registry is a pair of atomics, so it works without `std`.
+//!
+//! [`jni_on_load!`](crate::jni_on_load) registers the VM with the version returned by the
+//! library, and [`jni_on_unload!`](crate::jni_on_unload) unregisters it.
+
+use core::fmt;
+use core::ptr;
+use core::sync::atomic::{AtomicI32, AtomicPtr, Ordering};
+
+use crate::invocation::{
+    detach_current_thread, get_env_or_attach, EnvStatus, InvocationApi, JniError, JNI_VERSIONS,
+};
+use crate::{jint, JNIEnv, JavaVM};
+
+static VM: AtomicPtr<JavaVM> = AtomicPtr::new(ptr::null_mut());
+static VERSION: AtomicI32 = AtomicI32::new(0);
+
+/// A registered VM and the JNI version negotiated with it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisteredVm {
+    /// The VM
+    pub vm: *mut JavaVM,
+    /// The JNI version negotiated with the VM
+    pub version: jint,
+}
+
+/// Error returned by [`java_vm`] when no VM has been registered
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NotRegistered;
+
+impl fmt::Display for NotRegistered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no Java VM has been registered")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotRegistered {}
+
+/// Registers `vm` and the JNI version negotiated with it, replacing any registered VM
+///
+/// A null `vm` unregisters the VM. The VM and version are stored separately, so registering
+/// different VMs from several threads at once could pair one VM with another's version. A
+/// process only has one VM, so this only matters for the version.
+pub fn register_java_vm(vm: *mut JavaVM, version: jint) {
+    VERSION.store(version, Ordering::Relaxed);
+    VM.store(vm, Ordering::Release);
+}
+
+/// Unregisters the VM, returning it if one was registered
+pub fn unregister_java_vm() -> Option<RegisteredVm> {
+    let vm = VM.swap(ptr::null_mut(), Ordering::Acquire);
+    (!vm.is_null()).then(|| RegisteredVm {
+        vm,
+        version: VERSION.load(Ordering::Relaxed),
+    })
+}
+
+/// Unregisters `vm` if it's the registered VM, since it was destroyed
+pub(crate) fn unregister_destroyed_java_vm(vm: *mut JavaVM) {
+    let _ = VM.compare_exchange(vm, ptr::null_mut(), Ordering::AcqRel, Ordering::Relaxed);
+}
+
+/// Returns the registered VM
+pub fn java_vm() -> Result<RegisteredVm, NotRegistered> {
+    let vm = VM.load(Ordering::Acquire);
+    if vm.is_null() {
+        return Err(NotRegistered);
+    }
+    Ok(RegisteredVm {
+        vm,
+        version: VERSION.load(Ordering::Relaxed),
+    })
+}
+
+/// Registers the VM of `env` with the version reported by `GetVersion`
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread.
+pub unsafe fn register_java_vm_of_env(env: *mut JNIEnv) -> Result<RegisteredVm, JniError> {
+    let mut vm = ptr::null_mut();
+    JniError::check(((**env).v1_1.GetJavaVM)(env, &mut vm))?;
+    let version = ((**env).v1_1.GetVersion)(env);
+    register_java_vm(vm, version);
+    Ok(RegisteredVm { vm, version })
+}
+
+/// Registers the first VM created in this process, if there is one
+///
+/// The version is the newest of [`JNI_VERSIONS`] that the VM supports. If the current thread
+/// isn't attached, it's attached to find the version and then detached again.
+///
+/// # Safety
+///
+/// The functions must be the invocation API of a JVM library, and its VM must support at
+/// least JNI 1.2.
+pub unsafe fn register_created_java_vm(
+    api: &InvocationApi,
+) -> Result<Option<RegisteredVm>, JniError> {
+    let Some(vm) = api.created_java_vm()? else {
+        return Ok(None);
+    };
+    let newest = JNI_VERSIONS[0];
+    let version = match get_env_or_attach(vm, newest, None)? {
+        EnvStatus::AlreadyAttached(_) => newest,
+        EnvStatus::NewlyAttached(_) => {
+            detach_current_thread(vm)?;
+            newest
+        }
+        EnvStatus::UnsupportedVersion { highest } => highest.ok_or(JniError::Version)?,
+    };
+    register_java_vm(vm, version);
+    Ok(Some(RegisteredVm { vm, version }))
+}
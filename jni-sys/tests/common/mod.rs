@@ -0,0 +1,66 @@
+//! Creates the VM shared by the tests of a test binary
+#![allow(dead_code)]
+
+use jni_sys::invocation::{detach_current_thread, CreateOptions, InvocationApi, JniError};
+use jni_sys::{jint, JavaVM, JavaVMInitArgs, JNI_VERSION_10};
+use std::ptr;
+use std::sync::OnceLock;
+
+/// A version newer than any VM supports
+pub const FUTURE_VERSION: jint = 0x7fff0000;
+
+#[cfg(not(feature = "dynamic"))]
+pub fn api() -> InvocationApi {
+    InvocationApi::linked()
+}
+
+#[cfg(feature = "dynamic")]
+pub fn api() -> InvocationApi {
+    use std::ffi::CString;
+    use std::path::PathBuf;
+
+    static API: OnceLock<InvocationApi> = OnceLock::new();
+    *API.get_or_init(|| {
+        let java_home = PathBuf::from(std::env::var_os("JAVA_HOME").expect("JAVA_HOME not set"));
+        let path = if cfg!(windows) {
+            java_home.join("bin/server/jvm.dll")
+        } else if cfg!(target_os = "macos") {
+            java_home.join("lib/server/libjvm.dylib")
+        } else {
+            java_home.join("lib/server/libjvm.so")
+        };
+        let path = CString::new(path.into_os_string().into_string().unwrap()).unwrap();
+        unsafe { InvocationApi::load(&path) }.unwrap()
+    })
+}
+
+/// Returns the VM shared by all tests, creating it on first use
+pub fn java_vm() -> *mut JavaVM {
+    struct Vm(*mut JavaVM);
+    unsafe impl Send for Vm {}
+    unsafe impl Sync for Vm {}
+
+    static VM: OnceLock<Vm> = OnceLock::new();
+    VM.get_or_init(|| unsafe {
+        let args = JavaVMInitArgs {
+            version: FUTURE_VERSION,
+            nOptions: 0,
+            options: ptr::null_mut(),
+            ignoreUnrecognized: false,
+        };
+        assert_eq!(
+            api().create_java_vm(&args, CreateOptions::new()),
+            Err(JniError::Version)
+        );
+        let created = api()
+            .create_java_vm(&args, CreateOptions::new().retry_older_versions(true))
+            .unwrap();
+        assert!(!created.existing);
+        assert!(created.newly_attached);
+        assert!(created.version >= JNI_VERSION_10);
+        // The creating thread is attached; leave it free for the attach tests
+        detach_current_thread(created.vm).unwrap();
+        Vm(created.vm)
+    })
+    .0
+}
@@ -0,0 +1,50 @@
+//! Tests of creating a VM when one exists, which changes what `JNI_GetCreatedJavaVMs` reports
+//! on some JDKs
+#![cfg(any(feature = "link-jvm", feature = "dynamic"))]
+
+mod common;
+
+use common::{api, java_vm, FUTURE_VERSION};
+use jni_sys::invocation::{detach_current_thread, CreateOptions, JniError};
+use jni_sys::{JavaVMInitArgs, JNI_VERSION_10, JNI_VERSION_1_8};
+use std::ptr;
+use std::thread;
+
+#[test]
+fn create_existing_java_vm() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        let args = JavaVMInitArgs {
+            version: JNI_VERSION_1_8,
+            nOptions: 0,
+            options: ptr::null_mut(),
+            ignoreUnrecognized: false,
+        };
+        assert_eq!(
+            api().create_java_vm(&args, CreateOptions::new()),
+            Err(JniError::AlreadyExists)
+        );
+        let existing = api()
+            .create_java_vm(&args, CreateOptions::new().use_existing(true))
+            .unwrap();
+        assert_eq!(existing.vm, vm);
+        assert!(existing.existing);
+        assert!(existing.newly_attached);
+        assert!(existing.version >= JNI_VERSION_10);
+
+        let args = JavaVMInitArgs {
+            version: FUTURE_VERSION,
+            ..args
+        };
+        let options = CreateOptions::new().use_existing(true);
+        assert_eq!(api().create_java_vm(&args, options), Err(JniError::Version));
+        let again = api()
+            .create_java_vm(&args, options.retry_older_versions(true))
+            .unwrap();
+        assert_eq!(again.env, existing.env);
+        assert!(!again.newly_attached);
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
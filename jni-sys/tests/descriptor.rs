@@ -0,0 +1,112 @@
+use jni_sys::descriptor::{
+    DescriptorErrorKind, ElementType, FieldType, MethodDescriptor, PrimitiveType, ReturnType,
+    TypeKind,
+};
+
+#[test]
+fn field_descriptors() {
+    assert_eq!(
+        FieldType::parse("I"),
+        Ok(FieldType::Primitive(PrimitiveType::Int))
+    );
+    assert_eq!(
+        FieldType::parse("Ljava/lang/String;"),
+        Ok(FieldType::Class("java/lang/String"))
+    );
+    assert_eq!(
+        FieldType::parse("[[J"),
+        Ok(FieldType::Array {
+            dimensions: 2,
+            element: ElementType::Primitive(PrimitiveType::Long),
+        })
+    );
+    let array = FieldType::parse("[Ljava/util/Map$Entry;").unwrap();
+    assert_eq!(
+        array,
+        FieldType::Array {
+            dimensions: 1,
+            element: ElementType::Class("java/util/Map$Entry"),
+        }
+    );
+    assert_eq!(array.kind(), TypeKind::Object);
+    assert_eq!(array.to_string(), "[Ljava/util/Map$Entry;");
+    assert_eq!(FieldType::parse("D").unwrap().slots(), 2);
+}
+
+#[test]
+fn method_descriptors() {
+    let method = MethodDescriptor::parse("(IZLjava/lang/String;[[JD)[B").unwrap();
+    assert_eq!(method.param_count(), 5);
+    assert_eq!(method.param_slots(), 6);
+    assert_eq!(method.params_descriptor(), "IZLjava/lang/String;[[JD");
+    assert_eq!(method.return_kind(), TypeKind::Object);
+    let kinds: Vec<_> = method.params().map(|param| param.kind()).collect();
+    assert_eq!(
+        kinds,
+        [
+            TypeKind::Int,
+            TypeKind::Boolean,
+            TypeKind::Object,
+            TypeKind::Object,
+            TypeKind::Double
+        ]
+    );
+    assert_eq!(method.to_string(), "(IZLjava/lang/String;[[JD)[B");
+
+    let method = MethodDescriptor::parse("()V").unwrap();
+    assert_eq!(method.param_count(), 0);
+    assert_eq!(method.params().next(), None);
+    assert_eq!(method.return_type(), ReturnType::Void);
+    assert_eq!(method.return_kind(), TypeKind::Void);
+}
+
+#[test]
+fn invalid_descriptors() {
+    let cases: &[(&str, usize, DescriptorErrorKind)] = &[
+        ("", 0, DescriptorErrorKind::UnexpectedEnd),
+        ("I)V", 0, DescriptorErrorKind::UnexpectedChar('I')),
+        ("(I", 2, DescriptorErrorKind::UnexpectedEnd),
+        ("(I)", 3, DescriptorErrorKind::UnexpectedEnd),
+        ("(V)V", 1, DescriptorErrorKind::VoidParameter),
+        ("([V)V", 2, DescriptorErrorKind::VoidParameter),
+        ("(Q)V", 1, DescriptorErrorKind::UnexpectedChar('Q')),
+        (
+            "(Ljava/lang/String)V",
+            1,
+            DescriptorErrorKind::UnterminatedClassName,
+        ),
+        ("(L;)V", 2, DescriptorErrorKind::InvalidClassName),
+        (
+            "(Ljava//String;)V",
+            7,
+            DescriptorErrorKind::InvalidClassName,
+        ),
+        (
+            "(Ljava/lang/String/;)V",
+            19,
+            DescriptorErrorKind::InvalidClassName,
+        ),
+        (
+            "(Ljava.lang.String;)V",
+            6,
+            DescriptorErrorKind::InvalidClassName,
+        ),
+        ("()VV", 3, DescriptorErrorKind::TrailingData),
+        ("(I)é", 3, DescriptorErrorKind::UnexpectedChar('é')),
+    ];
+    for (descriptor, offset, kind) in cases {
+        let err = MethodDescriptor::parse(descriptor).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (*offset, *kind), "{descriptor}");
+    }
+
+    let too_deep = "[".repeat(256) + "I";
+    let err = FieldType::parse(&too_deep).unwrap_err();
+    assert_eq!(err.kind(), DescriptorErrorKind::TooManyDimensions);
+    assert!(FieldType::parse(&too_deep[1..]).is_ok());
+
+    let err = FieldType::parse("II").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected data after descriptor at offset 1"
+    );
+}
@@ -0,0 +1,24 @@
+//! Tests of destroying the VM, which leaves it unusable for any other test in the binary
+#![cfg(all(feature = "std", any(feature = "link-jvm", feature = "dynamic")))]
+
+mod common;
+
+use common::java_vm;
+use jni_sys::invocation::{attach_current_thread_until_exit, destroy_java_vm};
+use jni_sys::registry;
+use jni_sys::JNI_VERSION_1_8;
+use std::thread;
+
+#[test]
+fn destroy_with_thread_attached_until_exit() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        registry::register_java_vm(vm, JNI_VERSION_1_8);
+        attach_current_thread_until_exit(vm, JNI_VERSION_1_8, None).unwrap();
+        destroy_java_vm(vm).unwrap();
+        assert!(registry::java_vm().is_err());
+        // The thread exits without detaching from the destroyed VM
+    })
+    .join()
+    .unwrap();
+}
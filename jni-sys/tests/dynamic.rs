@@ -0,0 +1,72 @@
+#![cfg(feature = "dynamic")]
+
+use jni_sys::invocation::{InvocationApi, LoadError};
+use jni_sys::{JavaVMInitArgs, JNI_OK, JNI_VERSION_1_8};
+use std::ffi::{CStr, CString};
+use std::path::PathBuf;
+use std::ptr;
+
+fn libjvm() -> Option<CString> {
+    let java_home = PathBuf::from(std::env::var_os("JAVA_HOME")?);
+    let path = if cfg!(windows) {
+        java_home.join("bin/server/jvm.dll")
+    } else if cfg!(target_os = "macos") {
+        java_home.join("lib/server/libjvm.dylib")
+    } else {
+        java_home.join("lib/server/libjvm.so")
+    };
+    CString::new(path.into_os_string().into_string().ok()?).ok()
+}
+
+#[test]
+fn load_missing_library() {
+    let err = unsafe {
+        InvocationApi::load(CStr::from_bytes_with_nul(b"/nonexistent/libjvm.so\0").unwrap())
+    }
+    .unwrap_err();
+    let LoadError::Open(message) = err else {
+        panic!("unexpected error {err:?}");
+    };
+    if cfg!(unix) {
+        assert!(message.to_string().contains("/nonexistent/libjvm.so"));
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn load_library_without_symbols() {
+    let err = unsafe { InvocationApi::load(CStr::from_bytes_with_nul(b"libc.so.6\0").unwrap()) }
+        .unwrap_err();
+    assert_eq!(
+        err,
+        LoadError::MissingSymbol("JNI_GetDefaultJavaVMInitArgs")
+    );
+    assert_eq!(
+        err.to_string(),
+        "the JVM library doesn't export `JNI_GetDefaultJavaVMInitArgs`"
+    );
+}
+
+#[test]
+fn load_libjvm() {
+    let Some(path) = libjvm() else {
+        eprintln!("JAVA_HOME not set, skipping");
+        return;
+    };
+    let api = unsafe { InvocationApi::load(&path) }.unwrap();
+
+    let mut args = JavaVMInitArgs {
+        version: JNI_VERSION_1_8,
+        nOptions: 0,
+        options: ptr::null_mut(),
+        ignoreUnrecognized: false,
+    };
+    let status =
+        unsafe { (api.JNI_GetDefaultJavaVMInitArgs)(&mut args as *mut JavaVMInitArgs as *mut _) };
+    assert_eq!(status, JNI_OK);
+
+    let mut n_vms = -1;
+    let status = unsafe { (api.JNI_GetCreatedJavaVMs)(ptr::null_mut(), 0, &mut n_vms) };
+    assert_eq!(status, JNI_OK);
+    assert_eq!(n_vms, 0);
+}
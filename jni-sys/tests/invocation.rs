@@ -0,0 +1,85 @@
+use core::ffi::c_void;
+use jni_sys::invocation::{CreateOptions, InvocationApi, JniError};
+use jni_sys::{jint, jsize, JavaVM, JavaVMInitArgs, JNI_VERSION_1_8};
+use jni_sys::{JNI_EDETACHED, JNI_EEXIST, JNI_EINVAL, JNI_ENOMEM, JNI_ERR, JNI_EVERSION, JNI_OK};
+use std::ptr;
+
+#[test]
+fn jni_error_status() {
+    assert_eq!(JniError::check(JNI_OK), Ok(()));
+    for (status, err) in [
+        (JNI_ERR, JniError::Unknown),
+        (JNI_EDETACHED, JniError::Detached),
+        (JNI_EVERSION, JniError::Version),
+        (JNI_ENOMEM, JniError::NoMemory),
+        (JNI_EEXIST, JniError::AlreadyExists),
+        (JNI_EINVAL, JniError::InvalidArguments),
+        (-42, JniError::Other(-42)),
+    ] {
+        assert_eq!(JniError::check(status), Err(err));
+        assert_eq!(err.status(), status);
+    }
+}
+
+#[test]
+fn jni_error_display() {
+    assert_eq!(JniError::Version.to_string(), "JNI version error");
+    assert_eq!(JniError::Other(-42).to_string(), "JNI error -42");
+}
+
+#[test]
+fn jni_versions_newest_first() {
+    use jni_sys::invocation::JNI_VERSIONS;
+
+    assert!(JNI_VERSIONS.windows(2).all(|pair| pair[0] > pair[1]));
+    assert_eq!(JNI_VERSIONS.last(), Some(&jni_sys::JNI_VERSION_1_1));
+}
+
+/// Invocation API functions of a VM that refuses to create a VM, but doesn't report one
+mod no_created_vm {
+    use super::*;
+
+    pub unsafe extern "system" fn get_default_args(_args: *mut c_void) -> jint {
+        JNI_OK
+    }
+
+    pub unsafe extern "system" fn create(
+        _vm: *mut *mut JavaVM,
+        _env: *mut *mut c_void,
+        _args: *mut c_void,
+    ) -> jint {
+        JNI_EEXIST
+    }
+
+    pub unsafe extern "system" fn get_created(
+        _vms: *mut *mut JavaVM,
+        _len: jsize,
+        count: *mut jsize,
+    ) -> jint {
+        *count = 0;
+        JNI_OK
+    }
+}
+
+#[test]
+fn create_existing_without_created_vm() {
+    let api = InvocationApi {
+        JNI_GetDefaultJavaVMInitArgs: no_created_vm::get_default_args,
+        JNI_CreateJavaVM: no_created_vm::create,
+        JNI_GetCreatedJavaVMs: no_created_vm::get_created,
+    };
+    let args = JavaVMInitArgs {
+        version: JNI_VERSION_1_8,
+        nOptions: 0,
+        options: ptr::null_mut(),
+        ignoreUnrecognized: false,
+    };
+    for options in [
+        CreateOptions::new(),
+        CreateOptions::new().use_existing(true),
+    ] {
+        // No VM is registered in this test binary
+        let created = unsafe { api.create_java_vm(&args, options) };
+        assert_eq!(created.unwrap_err(), JniError::AlreadyExists);
+    }
+}
@@ -0,0 +1,45 @@
+use jni_sys::{jargs, jboolean, jint, jlong, jobject, jvalue};
+use std::ptr;
+
+#[test]
+fn jargs_trybuilds() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/trybuild/02-jargs-fail-arity.rs");
+    t.compile_fail("tests/trybuild/02-jargs-fail-kind.rs");
+    t.compile_fail("tests/trybuild/02-jargs-fail-descriptor.rs");
+}
+
+#[test]
+fn jargs_untyped() {
+    let obj: jobject = ptr::null_mut();
+    let args: [jvalue; 4] = jargs![42 as jint, 7i64, true, obj];
+    unsafe {
+        assert_eq!(args[0].i, 42);
+        assert_eq!(args[1].j, 7);
+        assert!(args[2].z);
+        assert!(args[3].l.is_null());
+    }
+    let empty: [jvalue; 0] = jargs![];
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn jargs_with_descriptor() {
+    let count: jint = 3;
+    let flag: jboolean = false;
+    let name: jobject = ptr::null_mut();
+    let stamp: jlong = -1;
+    let args = jargs!("(IZLjava/lang/String;[[JDF)V"; count, flag, name, name, stamp as f64, 1.5);
+    assert_eq!(args.len(), 6);
+    unsafe {
+        assert_eq!(args[0].i, 3);
+        assert!(!args[1].z);
+        assert!(args[2].l.is_null());
+        assert!(args[3].l.is_null());
+        assert_eq!(args[4].d, -1.0);
+        assert_eq!(args[5].f, 1.5);
+    }
+
+    let args = jargs!("()Ljava/lang/Object;";);
+    assert!(args.is_empty());
+}
@@ -0,0 +1,271 @@
+//! Tests that need a running VM, which can only be created once per process
+#![cfg(any(feature = "link-jvm", feature = "dynamic"))]
+
+mod common;
+
+use common::{api, java_vm, FUTURE_VERSION};
+use jni_sys::invocation::{
+    attach_current_thread, attach_current_thread_as_daemon, detach_current_thread,
+    get_env_or_attach, EnvStatus, JniError,
+};
+use jni_sys::{JNIEnv, JavaVMAttachArgs, JNI_VERSION_10, JNI_VERSION_1_2, JNI_VERSION_1_8};
+use std::ffi::CStr;
+use std::ptr;
+use std::thread;
+
+#[test]
+fn created_java_vms() {
+    let vm = java_vm();
+    let api = api();
+    unsafe {
+        assert_eq!(api.created_java_vm_count(), Ok(1));
+        assert_eq!(api.created_java_vm(), Ok(Some(vm)));
+
+        let vms = api.created_java_vms::<2>().unwrap();
+        assert_eq!(vms.as_slice(), [vm]);
+        assert_eq!(vms.total(), 1);
+        assert!(!vms.is_truncated());
+        assert_eq!(vms.into_iter().collect::<Vec<_>>(), [vm]);
+
+        let vms = api.created_java_vms::<0>().unwrap();
+        assert!(vms.is_empty());
+        assert!(vms.is_truncated());
+
+        let mut buf = [ptr::null_mut(); 4];
+        assert_eq!(api.created_java_vms_into(&mut buf).unwrap(), [vm]);
+
+        #[cfg(feature = "std")]
+        assert_eq!(api.all_created_java_vms().unwrap(), [vm]);
+    }
+
+    // The VM can be used through the `JNIInvokeInterface_` union
+    let mut env = ptr::null_mut();
+    let status = unsafe { ((**vm).v1_2.GetEnv)(vm, &mut env, JNI_VERSION_1_8) };
+    assert_eq!(status, jni_sys::JNI_EDETACHED);
+}
+
+/// Returns `Thread.currentThread().getName()`
+unsafe fn thread_name(env: *mut JNIEnv) -> String {
+    let class = ((**env).v1_1.FindClass)(env, b"java/lang/Thread\0".as_ptr().cast());
+    let current_thread = ((**env).v1_1.GetStaticMethodID)(
+        env,
+        class,
+        b"currentThread\0".as_ptr().cast(),
+        b"()Ljava/lang/Thread;\0".as_ptr().cast(),
+    );
+    let get_name = ((**env).v1_1.GetMethodID)(
+        env,
+        class,
+        b"getName\0".as_ptr().cast(),
+        b"()Ljava/lang/String;\0".as_ptr().cast(),
+    );
+    let thread = ((**env).v1_1.CallStaticObjectMethodA)(env, class, current_thread, ptr::null());
+    let name = ((**env).v1_1.CallObjectMethodA)(env, thread, get_name, ptr::null());
+    let chars = ((**env).v1_1.GetStringUTFChars)(env, name, ptr::null_mut());
+    let result = CStr::from_ptr(chars).to_str().unwrap().to_owned();
+    ((**env).v1_1.ReleaseStringUTFChars)(env, name, chars);
+    result
+}
+
+#[test]
+fn attach_and_detach() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        let name = CStr::from_bytes_with_nul(b"attach-test\0").unwrap();
+        let args = JavaVMAttachArgs::new(JNI_VERSION_1_8, name, None);
+        let attached = attach_current_thread(vm, JNI_VERSION_1_8, Some(&args)).unwrap();
+        assert!(attached.newly_attached);
+        assert!(!attached.env.is_null());
+        assert_eq!(thread_name(attached.env), "attach-test");
+
+        let again = attach_current_thread(vm, JNI_VERSION_1_8, None).unwrap();
+        assert!(!again.newly_attached);
+        assert_eq!(again.env, attached.env);
+
+        detach_current_thread(vm).unwrap();
+        let mut env = ptr::null_mut();
+        assert_eq!(
+            JniError::check(((**vm).v1_2.GetEnv)(vm, &mut env, JNI_VERSION_1_8)),
+            Err(JniError::Detached)
+        );
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn attach_as_daemon() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        assert_eq!(
+            attach_current_thread_as_daemon(vm, JNI_VERSION_1_2, None),
+            Err(JniError::Version)
+        );
+        let attached = attach_current_thread_as_daemon(vm, JNI_VERSION_1_8, None).unwrap();
+        assert!(attached.newly_attached);
+        detach_current_thread(vm).unwrap();
+
+        // The version of the arguments is replaced with the one the VM supports
+        let name = CStr::from_bytes_with_nul(b"daemon-test\0").unwrap();
+        let args = JavaVMAttachArgs::new(FUTURE_VERSION, name, None);
+        let attached = attach_current_thread_as_daemon(vm, JNI_VERSION_1_8, Some(&args)).unwrap();
+        assert!(attached.newly_attached);
+        assert_eq!(thread_name(attached.env), "daemon-test");
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn get_env_or_attach_status() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        let Ok(EnvStatus::UnsupportedVersion {
+            highest: Some(highest),
+        }) = get_env_or_attach(vm, FUTURE_VERSION, None)
+        else {
+            panic!("future version supported");
+        };
+        assert!(highest >= JNI_VERSION_10);
+        // The thread was detached again after probing
+        let mut env = ptr::null_mut();
+        assert_eq!(
+            JniError::check(((**vm).v1_2.GetEnv)(vm, &mut env, JNI_VERSION_1_8)),
+            Err(JniError::Detached)
+        );
+
+        let name = CStr::from_bytes_with_nul(b"get-env-or-attach\0").unwrap();
+        let args = JavaVMAttachArgs::new(JNI_VERSION_1_2, name, None);
+        let Ok(EnvStatus::NewlyAttached(env)) = get_env_or_attach(vm, highest, Some(&args)) else {
+            panic!("thread not attached");
+        };
+        assert_eq!(thread_name(env), "get-env-or-attach");
+        assert_eq!(
+            get_env_or_attach(vm, JNI_VERSION_1_8, None),
+            Ok(EnvStatus::AlreadyAttached(env))
+        );
+        assert_eq!(
+            get_env_or_attach(vm, FUTURE_VERSION, None),
+            Ok(EnvStatus::UnsupportedVersion {
+                highest: Some(highest)
+            })
+        );
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn register_java_vm() {
+    use jni_sys::registry;
+
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        let registered = registry::register_created_java_vm(&api()).unwrap().unwrap();
+        assert_eq!(registered.vm, vm);
+        assert!(registered.version >= JNI_VERSION_10);
+        assert_eq!(registry::java_vm(), Ok(registered));
+        // The thread was only attached to find the version
+        let mut env = ptr::null_mut();
+        assert_eq!(
+            JniError::check(((**vm).v1_2.GetEnv)(vm, &mut env, JNI_VERSION_1_8)),
+            Err(JniError::Detached)
+        );
+
+        let attached = attach_current_thread(vm, JNI_VERSION_1_8, None).unwrap();
+        let of_env = registry::register_java_vm_of_env(attached.env).unwrap();
+        assert_eq!(of_env, registered);
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
+
+/// A global reference that can be passed between test threads
+#[cfg(feature = "std")]
+struct GlobalRef(jni_sys::jobject);
+#[cfg(feature = "std")]
+unsafe impl Send for GlobalRef {}
+#[cfg(feature = "std")]
+unsafe impl Sync for GlobalRef {}
+
+/// Returns a global reference to `Thread.currentThread()`
+#[cfg(feature = "std")]
+unsafe fn current_thread(env: *mut JNIEnv) -> GlobalRef {
+    let class = ((**env).v1_1.FindClass)(env, b"java/lang/Thread\0".as_ptr().cast());
+    let current_thread = ((**env).v1_1.GetStaticMethodID)(
+        env,
+        class,
+        b"currentThread\0".as_ptr().cast(),
+        b"()Ljava/lang/Thread;\0".as_ptr().cast(),
+    );
+    let thread = ((**env).v1_1.CallStaticObjectMethodA)(env, class, current_thread, ptr::null());
+    GlobalRef(((**env).v1_1.NewGlobalRef)(env, thread))
+}
+
+/// Returns whether `thread` is still alive, which it is until it's detached
+#[cfg(feature = "std")]
+fn is_alive(thread: &GlobalRef) -> bool {
+    thread::scope(|scope| {
+        scope
+            .spawn(|| unsafe {
+                let vm = java_vm();
+                let env = attach_current_thread(vm, JNI_VERSION_1_8, None)
+                    .unwrap()
+                    .env;
+                let class = ((**env).v1_1.FindClass)(env, b"java/lang/Thread\0".as_ptr().cast());
+                let is_alive = ((**env).v1_1.GetMethodID)(
+                    env,
+                    class,
+                    b"isAlive\0".as_ptr().cast(),
+                    b"()Z\0".as_ptr().cast(),
+                );
+                let alive = ((**env).v1_1.CallBooleanMethodA)(env, thread.0, is_alive, ptr::null());
+                detach_current_thread(vm).unwrap();
+                alive
+            })
+            .join()
+            .unwrap()
+    })
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn attach_until_exit() {
+    use jni_sys::invocation::{attach_current_thread_until_exit, set_detach_on_exit};
+
+    let thread = thread::spawn(|| unsafe {
+        let vm = java_vm();
+        assert!(!set_detach_on_exit(true));
+        let env = attach_current_thread_until_exit(vm, JNI_VERSION_1_8, None).unwrap();
+        assert_eq!(
+            attach_current_thread_until_exit(vm, JNI_VERSION_1_8, None),
+            Ok(env)
+        );
+        assert_eq!(
+            attach_current_thread_until_exit(ptr::null_mut(), JNI_VERSION_1_8, None),
+            Err(JniError::InvalidArguments)
+        );
+        current_thread(env)
+    })
+    .join()
+    .unwrap();
+    assert!(!is_alive(&thread));
+
+    // A thread that was already attached is left attached
+    let thread = thread::spawn(|| unsafe {
+        let vm = java_vm();
+        let attached = attach_current_thread(vm, JNI_VERSION_1_8, None).unwrap();
+        let env = attach_current_thread_until_exit(vm, JNI_VERSION_1_8, None).unwrap();
+        assert_eq!(env, attached.env);
+        let thread = current_thread(env);
+        assert!(is_alive(&thread));
+        detach_current_thread(vm).unwrap();
+        thread
+    })
+    .join()
+    .unwrap();
+    assert!(!is_alive(&thread));
+}
@@ -0,0 +1,212 @@
+#![cfg(feature = "std")]
+
+use jni_sys::jdk::{Jdk, JdkSearch, JdkSource, Libjvm, Release};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const RELEASE_17: &str = r#"IMPLEMENTOR="Eclipse Adoptium"
+IMPLEMENTOR_VERSION="Temurin-17.0.9+9"
+JAVA_RUNTIME_VERSION="17.0.9+9"
+JAVA_VERSION="17.0.9"
+JAVA_VERSION_DATE="2023-10-17"
+LIBC="gnu"
+MODULES="java.base java.logging jdk.unsupported"
+OS_ARCH="x86_64"
+OS_NAME="Linux"
+"#;
+
+const RELEASE_8: &str = r#"JAVA_VERSION="1.8.0_392"
+OS_NAME="Linux"
+OS_VERSION="2.6"
+OS_ARCH="amd64"
+SOURCE=".:git:6d1a5f2b3c11"
+"#;
+
+/// Creates an empty directory for a test under Cargo's temporary directory
+fn scratch(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("jdk")
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, b"").unwrap();
+}
+
+fn fake_jdk17(home: &Path) {
+    fs::create_dir_all(home).unwrap();
+    fs::write(home.join("release"), RELEASE_17).unwrap();
+    touch(&home.join("bin/java"));
+    touch(&home.join("lib/server/libjvm.so"));
+    touch(&home.join("lib/client/libjvm.so"));
+    touch(&home.join("lib/minimal/libjvm.so"));
+    touch(&home.join("lib/libjava.so"));
+}
+
+fn fake_jdk8(home: &Path) {
+    fs::create_dir_all(home).unwrap();
+    fs::write(home.join("release"), RELEASE_8).unwrap();
+    touch(&home.join("bin/java"));
+    touch(&home.join("jre/lib/amd64/server/libjvm.so"));
+    touch(&home.join("jre/lib/amd64/jli/libjli.so"));
+}
+
+#[test]
+fn parse_release() {
+    let release = Release::parse(RELEASE_17);
+    assert_eq!(release.java_version(), Some("17.0.9"));
+    assert_eq!(release.java_runtime_version(), Some("17.0.9+9"));
+    assert_eq!(release.implementor(), Some("Eclipse Adoptium"));
+    assert_eq!(release.feature_version(), Some(17));
+    assert_eq!(
+        release.modules().collect::<Vec<_>>(),
+        ["java.base", "java.logging", "jdk.unsupported"]
+    );
+    assert_eq!(release.get("LIBC"), Some("gnu"));
+    assert_eq!(release.entries().count(), 9);
+
+    let release = Release::parse(RELEASE_8);
+    assert_eq!(release.feature_version(), Some(8));
+    assert_eq!(release.implementor(), None);
+    assert_eq!(release.modules().count(), 0);
+
+    let release = Release::parse("# comment\n\nJAVA_VERSION=21\nBOGUS\n  KEY = \"spaced\" \n");
+    assert_eq!(release.feature_version(), Some(21));
+    assert_eq!(release.get("KEY"), Some("spaced"));
+    assert_eq!(release.entries().count(), 2);
+}
+
+#[test]
+fn libjvm_variants() {
+    let root = scratch("variants");
+    let home = root.join("jdk-17");
+    fake_jdk17(&home);
+
+    let jdk = Jdk::at(&home, JdkSource::JavaHome).unwrap();
+    let home = home.canonicalize().unwrap();
+    assert_eq!(jdk.home, home);
+    let mut variants: Vec<_> = jdk.libjvms().into_iter().map(|l| l.variant).collect();
+    variants.sort();
+    assert_eq!(variants, ["client", "minimal", "server"]);
+    assert_eq!(
+        jdk.libjvm("client"),
+        Some(Libjvm {
+            variant: "client".to_string(),
+            path: home.join("lib/client/libjvm.so"),
+        })
+    );
+    assert_eq!(jdk.libjvm("zero"), None);
+    assert_eq!(jdk.default_libjvm().unwrap().variant, "server");
+
+    fs::remove_dir_all(home.join("lib/server")).unwrap();
+    assert_eq!(jdk.default_libjvm().unwrap().variant, "client");
+
+    let home = root.join("jdk-8");
+    fake_jdk8(&home);
+    let jdk = Jdk::at(&home, JdkSource::InstallRoot).unwrap();
+    assert_eq!(jdk.release.as_ref().unwrap().feature_version(), Some(8));
+    assert_eq!(
+        jdk.default_libjvm().unwrap().path,
+        jdk.home.join("jre/lib/amd64/server/libjvm.so")
+    );
+
+    let not_a_jdk = root.join("empty");
+    fs::create_dir_all(&not_a_jdk).unwrap();
+    assert_eq!(Jdk::at(&not_a_jdk, JdkSource::JavaHome), None);
+    assert_eq!(Jdk::at(root.join("missing"), JdkSource::JavaHome), None);
+}
+
+#[test]
+fn search() {
+    let root = scratch("search");
+    let installs = root.join("usr/lib/jvm");
+    fake_jdk17(&installs.join("temurin-17"));
+    fake_jdk8(&installs.join("openjdk-8"));
+    fs::create_dir_all(installs.join("not-a-jdk")).unwrap();
+    let home = root.join("opt/custom-jdk");
+    fake_jdk17(&home);
+
+    let path =
+        std::env::join_paths([root.join("missing/bin"), installs.join("openjdk-8/bin")]).unwrap();
+    let jdks = JdkSearch::new()
+        .java_home(&home)
+        .path(path)
+        .install_root(&installs)
+        .install_root(root.join("missing"))
+        .find();
+
+    let found: Vec<_> = jdks
+        .iter()
+        .map(|jdk| (jdk.home.clone(), jdk.source))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (home.canonicalize().unwrap(), JdkSource::JavaHome),
+            (
+                installs.join("openjdk-8").canonicalize().unwrap(),
+                JdkSource::Path
+            ),
+            (
+                installs.join("temurin-17").canonicalize().unwrap(),
+                JdkSource::InstallRoot
+            ),
+        ]
+    );
+    assert!(JdkSearch::new().find().is_empty());
+}
+
+#[test]
+fn search_default_libjvm() {
+    let root = scratch("default-libjvm");
+    let installs = root.join("usr/lib/jvm");
+    // Sorted by name, these would be found in the opposite order
+    fake_jdk8(&installs.join("java-1.8.0"));
+    fake_jdk17(&installs.join("java-17"));
+    let search = JdkSearch::new().install_root(&installs);
+    assert_eq!(
+        search.default_libjvm().unwrap().path,
+        installs
+            .join("java-17")
+            .canonicalize()
+            .unwrap()
+            .join("lib/server/libjvm.so")
+    );
+
+    // `JAVA_HOME` comes first even if it's older
+    let search = search.java_home(installs.join("java-1.8.0"));
+    assert_eq!(
+        search.default_libjvm().unwrap().path,
+        installs
+            .join("java-1.8.0")
+            .canonicalize()
+            .unwrap()
+            .join("jre/lib/amd64/server/libjvm.so")
+    );
+    assert_eq!(JdkSearch::new().default_libjvm(), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn search_path_through_links() {
+    let root = scratch("links");
+    let home = root.join("usr/lib/jvm/java-17");
+    fake_jdk17(&home);
+    fs::create_dir_all(root.join("etc/alternatives")).unwrap();
+    fs::create_dir_all(root.join("usr/bin")).unwrap();
+    std::os::unix::fs::symlink(home.join("bin/java"), root.join("etc/alternatives/java")).unwrap();
+    std::os::unix::fs::symlink(
+        root.join("etc/alternatives/java"),
+        root.join("usr/bin/java"),
+    )
+    .unwrap();
+
+    let jdks = JdkSearch::new().path(root.join("usr/bin")).find();
+    assert_eq!(jdks.len(), 1);
+    assert_eq!(jdks[0].home, home.canonicalize().unwrap());
+    assert_eq!(jdks[0].source, JdkSource::Path);
+}
@@ -0,0 +1,78 @@
+use jni_sys::{jboolean, jclass, jint, jintArray, jlong, jni_export, jobject, jstring, JNIEnv};
+use std::ptr;
+
+#[test]
+fn jni_export_trybuilds() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/trybuild/03-jni-export-fail-env.rs");
+    t.compile_fail("tests/trybuild/03-jni-export-fail-receiver.rs");
+    t.compile_fail("tests/trybuild/03-jni-export-fail-overload-type.rs");
+}
+
+#[jni_export(class = "com.example.Foo")]
+extern "system" fn answer(_env: *mut JNIEnv, _this: jobject) -> jint {
+    42
+}
+
+#[jni_export(class = "com/example/Foo", method = "answer", overloaded = true)]
+unsafe extern "system" fn answer_with(
+    _env: *mut JNIEnv,
+    _this: jobject,
+    name: jstring,
+    values: jintArray,
+    offset: jlong,
+) -> jint {
+    assert!(name.is_null() && values.is_null());
+    offset as jint
+}
+
+#[jni_export(class = "com.example.Foo$Inner", overloaded = true)]
+extern "system" fn is_ready(_env: *mut JNIEnv, _class: jclass) -> jboolean {
+    true
+}
+
+#[jni_export(
+    class = "com.example.Ünï",
+    method = "visit",
+    overloaded = true,
+    descriptor = "(Lcom/example/Visitor;I)V"
+)]
+extern "system" fn visit(_env: *mut JNIEnv, _this: jobject, _visitor: jobject, _depth: jint) {}
+
+extern "system" {
+    fn Java_com_example_Foo_answer(env: *mut JNIEnv, this: jobject) -> jint;
+    fn Java_com_example_Foo_answer__Ljava_lang_String_2_3IJ(
+        env: *mut JNIEnv,
+        this: jobject,
+        name: jstring,
+        values: jintArray,
+        offset: jlong,
+    ) -> jint;
+    fn Java_com_example_Foo_00024Inner_is_1ready__(env: *mut JNIEnv, class: jclass) -> jboolean;
+    fn Java_com_example__000dcn_000ef_visit__Lcom_example_Visitor_2I(
+        env: *mut JNIEnv,
+        this: jobject,
+        visitor: jobject,
+        depth: jint,
+    );
+}
+
+#[test]
+fn exported_symbols() {
+    let env = ptr::null_mut();
+    let obj = ptr::null_mut();
+    unsafe {
+        assert_eq!(Java_com_example_Foo_answer(env, obj), 42);
+        assert_eq!(
+            Java_com_example_Foo_answer__Ljava_lang_String_2_3IJ(env, obj, obj, obj, 7),
+            7
+        );
+        assert!(Java_com_example_Foo_00024Inner_is_1ready__(env, obj));
+        Java_com_example__000dcn_000ef_visit__Lcom_example_Visitor_2I(env, obj, obj, 1);
+    }
+    // The functions are still callable by their Rust names
+    assert_eq!(answer(env, obj), 42);
+    assert!(is_ready(env, obj));
+    visit(env, obj, obj, 1);
+    assert_eq!(unsafe { answer_with(env, obj, obj, obj, 1) }, 1);
+}
@@ -48,3 +48,24 @@ fn jni_env_union() {
         std::mem::size_of::<*mut c_void>() * NUM_JNI_ENV_MEMBERS
     );
 }
+
+#[test]
+fn jni_to_union_default_version() {
+    #[repr(C)]
+    #[jni_to_union(version_prefix = "JVMTI_VERSION", default_version = "1.0")]
+    pub struct MyInterface {
+        pub FunctionA: unsafe extern "system" fn(env: *mut JNIEnv) -> jint,
+
+        #[jni_added("9")]
+        pub FunctionB: unsafe extern "system" fn(env: *mut JNIEnv) -> jint,
+    }
+
+    assert_eq!(
+        std::mem::size_of::<MyInterface_1>(),
+        std::mem::size_of::<*mut c_void>()
+    );
+    assert_eq!(
+        std::mem::size_of::<MyInterface_9>(),
+        std::mem::size_of::<*mut c_void>() * 2
+    );
+}
@@ -0,0 +1,107 @@
+use jni_sys::jvmti::agent::{self, AgentOptions};
+use jni_sys::jvmti::{JVMTI_PHASE_DEAD, JVMTI_PHASE_LIVE, JVMTI_PHASE_ONLOAD};
+use jni_sys::{jint, JavaVM};
+use std::ffi::{c_char, c_void, CStr};
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static UNLOADED: AtomicBool = AtomicBool::new(false);
+
+unsafe fn on_load(_vm: *mut JavaVM, options: *mut c_char, _reserved: *mut c_void) -> jint {
+    match AgentOptions::from_ptr(options) {
+        Ok(options) => options
+            .value("status")
+            .map_or(0, |status| status.parse().unwrap()),
+        Err(_) => -1,
+    }
+}
+
+fn on_unload(_vm: *mut JavaVM) {
+    UNLOADED.store(true, Ordering::SeqCst);
+}
+
+jni_sys::agent_on_load!(on_load);
+jni_sys::agent_on_load!(on_load, "agent_test");
+jni_sys::agent_on_attach!(on_load);
+jni_sys::agent_on_attach!(on_load, "agent_test");
+jni_sys::agent_on_unload!(on_unload);
+jni_sys::agent_on_unload!(on_unload, "agent_test");
+
+extern "system" {
+    fn Agent_OnLoad(vm: *mut JavaVM, options: *mut c_char, reserved: *mut c_void) -> jint;
+    fn Agent_OnLoad_agent_test(
+        vm: *mut JavaVM,
+        options: *mut c_char,
+        reserved: *mut c_void,
+    ) -> jint;
+    fn Agent_OnAttach(vm: *mut JavaVM, options: *mut c_char, reserved: *mut c_void) -> jint;
+    fn Agent_OnAttach_agent_test(
+        vm: *mut JavaVM,
+        options: *mut c_char,
+        reserved: *mut c_void,
+    ) -> jint;
+    fn Agent_OnUnload(vm: *mut JavaVM);
+    fn Agent_OnUnload_agent_test(vm: *mut JavaVM);
+}
+
+#[test]
+fn entry_points() {
+    let vm = NonNull::<JavaVM>::dangling().as_ptr();
+    let options = |options: &[u8]| {
+        CStr::from_bytes_with_nul(options)
+            .unwrap()
+            .as_ptr()
+            .cast_mut()
+    };
+    let reserved = ptr::null_mut();
+    assert_eq!(agent::entry_phase(), None);
+    unsafe {
+        assert_eq!(Agent_OnLoad(vm, ptr::null_mut(), reserved), 0);
+        assert_eq!(agent::entry_phase(), Some(JVMTI_PHASE_ONLOAD));
+        let status = options(b"verbose,status=3\0");
+        assert_eq!(Agent_OnLoad_agent_test(vm, status, reserved), 3);
+
+        assert_eq!(Agent_OnAttach(vm, options(b"\xff\0"), reserved), -1);
+        assert_eq!(agent::entry_phase(), Some(JVMTI_PHASE_LIVE));
+        assert_eq!(Agent_OnAttach_agent_test(vm, status, reserved), 3);
+
+        Agent_OnUnload(vm);
+        assert_eq!(agent::entry_phase(), Some(JVMTI_PHASE_DEAD));
+        assert!(UNLOADED.swap(false, Ordering::SeqCst));
+        Agent_OnUnload_agent_test(vm);
+        assert!(UNLOADED.load(Ordering::SeqCst));
+    }
+}
+
+#[test]
+fn options() {
+    let options = AgentOptions::new("transport=dt_socket,server=y,,suspend=n,server=n,trace");
+    assert_eq!(
+        options.iter().collect::<Vec<_>>(),
+        [
+            ("transport", Some("dt_socket")),
+            ("server", Some("y")),
+            ("suspend", Some("n")),
+            ("server", Some("n")),
+            ("trace", None),
+        ]
+    );
+    assert_eq!(options.value("server"), Some("n"));
+    assert_eq!(options.value("trace"), None);
+    assert_eq!(options.value("missing"), None);
+    assert!(options.flag("trace"));
+    assert!(!options.flag("server"));
+    assert!(!options.is_empty());
+    assert_eq!(
+        options.as_str(),
+        "transport=dt_socket,server=y,,suspend=n,server=n,trace"
+    );
+
+    assert!(AgentOptions::new(",,").is_empty());
+    assert_eq!(
+        AgentOptions::new("key=").iter().collect::<Vec<_>>(),
+        [("key", Some(""))]
+    );
+    let options = unsafe { AgentOptions::from_ptr(ptr::null()) }.unwrap();
+    assert!(options.is_empty());
+}
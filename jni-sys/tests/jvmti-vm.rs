@@ -0,0 +1,174 @@
+//! Tests of JVMTI environments of a running VM
+#![cfg(any(feature = "link-jvm", feature = "dynamic"))]
+
+mod common;
+
+use common::{java_vm, FUTURE_VERSION};
+use jni_sys::invocation::{attach_current_thread, detach_current_thread, JniError};
+use jni_sys::jvmti::agent::{current_phase, get_jvmti_env};
+use jni_sys::jvmti::extensions::{
+    ExtensionEvents, ExtensionFunctions, ParamSignature, SignatureError,
+};
+use jni_sys::jvmti::memory::JvmtiBuffer;
+use jni_sys::jvmti::*;
+use jni_sys::{jboolean, jint, JavaVMAttachArgs, JNI_VERSION_1_8};
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::thread;
+
+#[test]
+fn jvmti_env() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        attach_current_thread(vm, JNI_VERSION_1_8, None).unwrap();
+        assert_eq!(get_jvmti_env(vm, FUTURE_VERSION), Err(JniError::Version));
+
+        let jvmti = get_jvmti_env(vm, JVMTI_VERSION_1_2).unwrap();
+        assert_eq!(current_phase(jvmti), Ok(JVMTI_PHASE_LIVE));
+        let mut version = 0;
+        ((**jvmti).v1.GetVersionNumber)(jvmti, &mut version)
+            .check()
+            .unwrap();
+        assert!(version >= JVMTI_VERSION_11);
+
+        ((**jvmti).v1.DisposeEnvironment)(jvmti).check().unwrap();
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn jvmti_buffers() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        let name = CStr::from_bytes_with_nul(b"jvmti-test\0").unwrap();
+        let args = JavaVMAttachArgs::new(JNI_VERSION_1_8, name, None);
+        let attached = attach_current_thread(vm, JNI_VERSION_1_8, Some(&args)).unwrap();
+        let jni = attached.env;
+        let jvmti = get_jvmti_env(vm, JVMTI_VERSION_1_2).unwrap();
+
+        let threads = JvmtiBuffer::from_call(jvmti, |count, threads| {
+            ((**jvmti).v1.GetAllThreads)(jvmti, count, threads)
+        })
+        .unwrap();
+        assert!(!threads.is_empty());
+
+        let negative = JvmtiBuffer::<jthread>::from_call(jvmti, |count, _| {
+            *count = -1;
+            JVMTI_ERROR_NONE
+        });
+        assert_eq!(negative.unwrap_err(), JVMTI_ERROR_INTERNAL);
+
+        let stacks = JvmtiBuffer::from_call(jvmti, |count, stacks| {
+            ((**jvmti).v1.GetAllStackTraces)(jvmti, 4, stacks, count)
+        })
+        .unwrap();
+        assert_eq!(stacks.len(), threads.len());
+        let mut names = vec![];
+        for (i, stack) in stacks.iter().enumerate() {
+            assert!(stacks.frames(i).len() <= 4);
+            assert_eq!(stacks.frames(i).len(), stack.frame_count as usize);
+
+            let mut info = MaybeUninit::<jvmtiThreadInfo>::uninit();
+            ((**jvmti).v1.GetThreadInfo)(jvmti, stack.thread, info.as_mut_ptr())
+                .check()
+                .unwrap();
+            let info = info.assume_init();
+            let name = JvmtiBuffer::from_c_string(jvmti, info.name);
+            names.push(name.to_c_str().unwrap().to_str().unwrap().to_owned());
+            ((**jni).v1_1.DeleteLocalRef)(jni, info.thread_group);
+            ((**jni).v1_1.DeleteLocalRef)(jni, info.context_class_loader);
+        }
+        assert!(names.iter().any(|name| name == "jvmti-test"), "{names:?}");
+
+        let (ptr, len) = threads.into_raw_parts();
+        assert_eq!(len, stacks.len());
+        drop(JvmtiBuffer::from_raw_parts(jvmti, ptr, len));
+        drop(JvmtiBuffer::<jthread>::from_raw_parts(
+            jvmti,
+            ptr::null_mut(),
+            0,
+        ));
+
+        ((**jvmti).v1.DisposeEnvironment)(jvmti).check().unwrap();
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn jvmti_extensions() {
+    thread::spawn(|| unsafe {
+        let vm = java_vm();
+        attach_current_thread(vm, JNI_VERSION_1_8, None).unwrap();
+        let jvmti = get_jvmti_env(vm, JVMTI_VERSION_1_2).unwrap();
+
+        let functions = ExtensionFunctions::get(jvmti).unwrap();
+        assert!(functions.find("com.example.Missing").is_none());
+        let function = functions
+            .find("com.sun.hotspot.functions.IsClassUnloadingEnabled")
+            .unwrap();
+        assert!(!function.short_description().to_bytes().is_empty());
+        assert_eq!(function.params().len(), 1);
+        assert_eq!(
+            function.param_name(0).to_str(),
+            Ok("IsClassUnloadingEnabled")
+        );
+
+        let out_boolean = ParamSignature::new(JVMTI_KIND_OUT, JVMTI_TYPE_JBOOLEAN);
+        let is_enabled = function.typed::<(*mut jboolean,)>(&[out_boolean]).unwrap();
+        let mut enabled = false;
+        is_enabled.call(jvmti, (&mut enabled,)).unwrap();
+        assert!(enabled);
+
+        let in_int = ParamSignature::new(JVMTI_KIND_IN, JVMTI_TYPE_JINT);
+        assert_eq!(
+            function.typed::<(jint,)>(&[in_int]).unwrap_err(),
+            SignatureError::Param {
+                index: 0,
+                expected: in_int,
+                found: out_boolean,
+            }
+        );
+        assert_eq!(
+            function.typed::<()>(&[out_boolean]).unwrap_err(),
+            SignatureError::ArgCount {
+                expected: 1,
+                found: 0,
+            }
+        );
+        assert_eq!(
+            function.check_params(&[]),
+            Err(SignatureError::ParamCount {
+                expected: 0,
+                found: 1,
+            })
+        );
+
+        let events = ExtensionEvents::get(jvmti).unwrap();
+        assert!(!events.as_slice().is_empty());
+        let class_unload = events.find("com.sun.hotspot.events.ClassUnload").unwrap();
+        let class_unload_params: Vec<_> = class_unload
+            .params()
+            .iter()
+            .map(ParamSignature::from)
+            .collect();
+        assert_eq!(
+            class_unload_params,
+            [
+                ParamSignature::new(JVMTI_KIND_IN_PTR, JVMTI_TYPE_JNIENV),
+                ParamSignature::new(JVMTI_KIND_IN_PTR, JVMTI_TYPE_CCHAR),
+            ]
+        );
+        drop(events);
+        drop(functions);
+
+        ((**jvmti).v1.DisposeEnvironment)(jvmti).check().unwrap();
+        detach_current_thread(vm).unwrap();
+    })
+    .join()
+    .unwrap();
+}
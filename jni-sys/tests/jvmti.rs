@@ -0,0 +1,236 @@
+use jni_sys::jvmti::*;
+use std::mem::size_of;
+use std::os::raw::c_void;
+
+const NUM_JVMTI_ENV_MEMBERS: usize = 156;
+#[test]
+fn jvmti_env_union() {
+    assert_eq!(
+        size_of::<jvmtiInterface_1_>(),
+        size_of::<*mut c_void>() * NUM_JVMTI_ENV_MEMBERS
+    );
+    // Each version ends with the last function it added
+    assert_eq!(
+        size_of::<jvmtiInterface_1__1>(),
+        size_of::<*mut c_void>() * 154
+    );
+    assert_eq!(
+        size_of::<jvmtiInterface_1__1_2>(),
+        size_of::<*mut c_void>() * 155
+    );
+    assert_eq!(
+        size_of::<jvmtiInterface_1__11>(),
+        size_of::<*mut c_void>() * 156
+    );
+}
+
+#[test]
+fn jvmti_versions() {
+    assert_eq!(JVMTI_VERSION_1, JVMTI_VERSION_1_0);
+    for version in [
+        JVMTI_VERSION_1_0,
+        JVMTI_VERSION_1_1,
+        JVMTI_VERSION_1_2,
+        JVMTI_VERSION_9,
+        JVMTI_VERSION_11,
+        JVMTI_VERSION_19,
+        JVMTI_VERSION_21,
+    ] {
+        assert_eq!(
+            version & JVMTI_VERSION_MASK_INTERFACE_TYPE,
+            JVMTI_VERSION_INTERFACE_JVMTI
+        );
+    }
+    assert_eq!(
+        (JVMTI_VERSION_21 & JVMTI_VERSION_MASK_MAJOR) >> JVMTI_VERSION_SHIFT_MAJOR,
+        21
+    );
+}
+
+#[test]
+fn capabilities() {
+    use jni_sys::jvmti::capabilities::Capability;
+
+    assert_eq!(size_of::<jvmtiCapabilities>(), 16);
+
+    let mut capabilities = jvmtiCapabilities::new();
+    assert!(capabilities.is_empty());
+    capabilities.set_can_tag_objects(true);
+    capabilities.set_can_support_virtual_threads(true);
+    assert!(capabilities.can_tag_objects());
+    assert!(capabilities.has(Capability::CanSupportVirtualThreads));
+    assert!(!capabilities.can_pop_frame());
+    assert_eq!(
+        capabilities.iter().collect::<Vec<_>>(),
+        [
+            Capability::CanTagObjects,
+            Capability::CanSupportVirtualThreads
+        ]
+    );
+    assert_eq!(
+        format!("{capabilities:?}"),
+        "{CanTagObjects, CanSupportVirtualThreads}"
+    );
+
+    let potential: jvmtiCapabilities = [Capability::CanTagObjects, Capability::CanPopFrame]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        capabilities.union(&potential),
+        capabilities | Capability::CanPopFrame.into()
+    );
+    assert_eq!(
+        capabilities.intersection(&potential),
+        Capability::CanTagObjects.into()
+    );
+    assert_eq!(
+        capabilities - potential,
+        Capability::CanSupportVirtualThreads.into()
+    );
+    assert!(!capabilities.is_subset(&potential));
+    assert!(capabilities.intersection(&potential).is_subset(&potential));
+
+    capabilities.set_can_tag_objects(false);
+    assert_eq!(capabilities, Capability::CanSupportVirtualThreads.into());
+    assert_eq!(Capability::ALL.len(), 45);
+    assert_eq!(
+        Capability::CanGenerateEarlyVmstart.name(),
+        "can_generate_early_vmstart"
+    );
+}
+
+#[test]
+fn events() {
+    use jni_sys::jvmti::events::EventCallbacks;
+    use jni_sys::JNIEnv;
+
+    unsafe extern "system" fn method_entry(
+        _jvmti: *mut jvmtiEnv,
+        _jni: *mut JNIEnv,
+        _thread: jthread,
+        _method: jni_sys::jmethodID,
+    ) {
+    }
+
+    unsafe extern "system" fn vm_death(_jvmti: *mut jvmtiEnv, _jni: *mut JNIEnv) {}
+
+    assert_eq!(JVMTI_EVENT_VM_INIT.raw(), 50);
+    assert_eq!(jvmtiEvent::from_raw(67), JVMTI_EVENT_NATIVE_METHOD_BIND);
+    assert_eq!(
+        JVMTI_EVENT_METHOD_EXIT.name(),
+        Some("JVMTI_EVENT_METHOD_EXIT")
+    );
+    assert_eq!(jvmtiEvent::from_raw(72).name(), None);
+    assert_eq!(
+        format!("{:?}", JVMTI_EVENT_VM_DEATH),
+        "JVMTI_EVENT_VM_DEATH"
+    );
+    assert_eq!(format!("{:?}", jvmtiEvent::from_raw(72)), "jvmtiEvent(72)");
+    assert_eq!(jvmtiEvent::ALL.first(), Some(&JVMTI_MIN_EVENT_TYPE_VAL));
+    assert_eq!(jvmtiEvent::ALL.last(), Some(&JVMTI_MAX_EVENT_TYPE_VAL));
+    assert!(jvmtiEvent::ALL
+        .windows(2)
+        .all(|events| events[0] < events[1]));
+
+    let callbacks = EventCallbacks::new()
+        .vm_death(Some(vm_death))
+        .method_entry(Some(method_entry));
+    assert!(callbacks.is_set(JVMTI_EVENT_METHOD_ENTRY));
+    assert!(!callbacks.is_set(JVMTI_EVENT_METHOD_EXIT));
+    assert_eq!(
+        callbacks.events().collect::<Vec<_>>(),
+        [JVMTI_EVENT_VM_DEATH, JVMTI_EVENT_METHOD_ENTRY]
+    );
+    assert!(callbacks.callbacks().MethodEntry.is_some());
+    assert!(callbacks
+        .method_entry(None)
+        .events()
+        .eq([JVMTI_EVENT_VM_DEATH]));
+
+    // One pointer per event from `JVMTI_EVENT_VM_INIT`, including the reserved ones
+    let count = (JVMTI_MAX_EVENT_TYPE_VAL.raw() - JVMTI_MIN_EVENT_TYPE_VAL.raw() + 1) as usize;
+    assert_eq!(
+        EventCallbacks::size() as usize,
+        size_of::<*mut c_void>() * count
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(JVMTI_ERROR_NONE.check(), Ok(()));
+    assert_eq!(
+        jvmtiError::from_raw(112).check(),
+        Err(JVMTI_ERROR_WRONG_PHASE)
+    );
+    assert_eq!(JVMTI_ERROR_MAX.raw(), 116);
+    assert_eq!(
+        JVMTI_ERROR_MUST_POSSESS_CAPABILITY.name(),
+        Some("JVMTI_ERROR_MUST_POSSESS_CAPABILITY")
+    );
+    assert_eq!(
+        JVMTI_ERROR_NULL_POINTER.description(),
+        Some("unexpected null pointer")
+    );
+    assert_eq!(
+        JVMTI_ERROR_WRONG_PHASE.to_string(),
+        "functionality not available in the current phase (JVMTI_ERROR_WRONG_PHASE)"
+    );
+    assert_eq!(
+        format!("{:?}", JVMTI_ERROR_INTERNAL),
+        "JVMTI_ERROR_INTERNAL"
+    );
+
+    let unknown = jvmtiError::from_raw(42);
+    assert_eq!(unknown.name(), None);
+    assert_eq!(unknown.description(), None);
+    assert_eq!(unknown.to_string(), "JVMTI error 42");
+    assert_eq!(format!("{unknown:?}"), "jvmtiError(42)");
+}
+
+#[test]
+fn heap_reference() {
+    use jni_sys::jvmti::heap::{heap_reference_kind_name, HeapReference};
+    use std::ptr;
+
+    let mut info: jvmtiHeapReferenceInfo = unsafe { std::mem::zeroed() };
+    info.field = jvmtiHeapReferenceInfoField { index: 3 };
+    let reference = unsafe { HeapReference::new(JVMTI_HEAP_REFERENCE_STATIC_FIELD, &info) };
+    assert!(matches!(reference, HeapReference::StaticField(field) if field.index == 3));
+    assert_eq!(reference.kind(), JVMTI_HEAP_REFERENCE_STATIC_FIELD);
+    assert_eq!(reference.index(), Some(3));
+    assert!(!reference.is_root());
+
+    info.stack_local = jvmtiHeapReferenceInfoStackLocal {
+        thread_tag: 1,
+        thread_id: 2,
+        depth: 3,
+        method: ptr::null_mut(),
+        location: 4,
+        slot: 5,
+    };
+    let reference = unsafe { HeapReference::new(JVMTI_HEAP_REFERENCE_STACK_LOCAL, &info) };
+    let HeapReference::StackLocal(local) = reference else {
+        panic!("{reference:?}");
+    };
+    assert_eq!((local.depth, local.location, local.slot), (3, 4, 5));
+    assert_eq!(reference.index(), None);
+    assert!(reference.is_root());
+
+    let reference = unsafe { HeapReference::new(JVMTI_HEAP_REFERENCE_CLASS, ptr::null()) };
+    assert!(matches!(
+        reference,
+        HeapReference::Other(JVMTI_HEAP_REFERENCE_CLASS)
+    ));
+    assert_eq!(
+        heap_reference_kind_name(reference.kind()),
+        Some("JVMTI_HEAP_REFERENCE_CLASS")
+    );
+    assert_eq!(heap_reference_kind_name(11), None);
+
+    let callbacks = jvmtiHeapCallbacks::default();
+    assert!(callbacks.heap_reference_callback.is_none());
+    assert_eq!(
+        size_of::<jvmtiHeapCallbacks>(),
+        size_of::<*mut c_void>() * 16
+    );
+}
@@ -0,0 +1,91 @@
+use jni_sys::lifecycle::{checked_on_load_version, checked_static_on_load_version};
+use jni_sys::registry::{self, NotRegistered, RegisteredVm};
+use jni_sys::{jint, JavaVM, JNI_ERR, JNI_EVERSION, JNI_VERSION_1_6, JNI_VERSION_1_8};
+use std::ffi::c_void;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+static LOAD_VERSION: AtomicI32 = AtomicI32::new(JNI_VERSION_1_8);
+static UNLOADED: AtomicBool = AtomicBool::new(false);
+
+fn on_load(_vm: *mut JavaVM, _reserved: *mut c_void) -> jint {
+    LOAD_VERSION.load(Ordering::SeqCst)
+}
+
+unsafe fn on_unload(_vm: *mut JavaVM, _reserved: *mut c_void) {
+    UNLOADED.store(true, Ordering::SeqCst);
+}
+
+jni_sys::jni_on_load!(on_load);
+jni_sys::jni_on_load!(on_load, "lifecycle_test");
+jni_sys::jni_on_unload!(on_unload);
+jni_sys::jni_on_unload!(on_unload, "lifecycle_test");
+
+extern "system" {
+    fn JNI_OnLoad(vm: *mut JavaVM, reserved: *mut c_void) -> jint;
+    fn JNI_OnLoad_lifecycle_test(vm: *mut JavaVM, reserved: *mut c_void) -> jint;
+    fn JNI_OnUnload(vm: *mut JavaVM, reserved: *mut c_void);
+    fn JNI_OnUnload_lifecycle_test(vm: *mut JavaVM, reserved: *mut c_void);
+}
+
+#[test]
+fn lifecycle_trybuilds() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/trybuild/04-on-load-fail-signature.rs");
+}
+
+#[test]
+fn checked_versions() {
+    assert_eq!(checked_on_load_version(JNI_VERSION_1_6), JNI_VERSION_1_6);
+    assert_eq!(checked_on_load_version(JNI_EVERSION), JNI_EVERSION);
+    assert_eq!(checked_on_load_version(0x00010003), JNI_ERR);
+    assert_eq!(checked_on_load_version(0), JNI_ERR);
+
+    assert_eq!(
+        checked_static_on_load_version(JNI_VERSION_1_8),
+        JNI_VERSION_1_8
+    );
+    assert_eq!(checked_static_on_load_version(JNI_VERSION_1_6), JNI_ERR);
+}
+
+#[test]
+fn exported_entry_points() {
+    unsafe {
+        assert_eq!(
+            JNI_OnLoad(ptr::null_mut(), ptr::null_mut()),
+            JNI_VERSION_1_8
+        );
+        assert_eq!(
+            JNI_OnLoad_lifecycle_test(ptr::null_mut(), ptr::null_mut()),
+            JNI_VERSION_1_8
+        );
+
+        LOAD_VERSION.store(JNI_VERSION_1_6, Ordering::SeqCst);
+        assert_eq!(
+            JNI_OnLoad(ptr::null_mut(), ptr::null_mut()),
+            JNI_VERSION_1_6
+        );
+        assert_eq!(
+            JNI_OnLoad_lifecycle_test(ptr::null_mut(), ptr::null_mut()),
+            JNI_ERR
+        );
+        LOAD_VERSION.store(JNI_VERSION_1_8, Ordering::SeqCst);
+
+        // The VM is registered by a successful load and unregistered by an unload
+        let vm = NonNull::<JavaVM>::dangling().as_ptr();
+        assert_eq!(JNI_OnLoad(vm, ptr::null_mut()), JNI_VERSION_1_8);
+        assert_eq!(
+            registry::java_vm(),
+            Ok(RegisteredVm {
+                vm,
+                version: JNI_VERSION_1_8
+            })
+        );
+
+        JNI_OnUnload(vm, ptr::null_mut());
+        assert!(UNLOADED.swap(false, Ordering::SeqCst));
+        assert_eq!(registry::java_vm(), Err(NotRegistered));
+        JNI_OnUnload_lifecycle_test(ptr::null_mut(), ptr::null_mut());
+        assert!(UNLOADED.load(Ordering::SeqCst));
+    }
+}
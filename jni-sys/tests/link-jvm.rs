@@ -0,0 +1,24 @@
+#![cfg(all(feature = "link-jvm", not(feature = "dynamic")))]
+
+use jni_sys::invocation::InvocationApi;
+use jni_sys::{JNI_GetDefaultJavaVMInitArgs, JavaVMInitArgs, JNI_OK, JNI_VERSION_1_8};
+use std::ptr;
+
+#[test]
+fn linked_invocation_api() {
+    let mut args = JavaVMInitArgs {
+        version: JNI_VERSION_1_8,
+        nOptions: 0,
+        options: ptr::null_mut(),
+        ignoreUnrecognized: false,
+    };
+    let status =
+        unsafe { JNI_GetDefaultJavaVMInitArgs(&mut args as *mut JavaVMInitArgs as *mut _) };
+    assert_eq!(status, JNI_OK);
+
+    let api = InvocationApi::linked();
+    let mut n_vms = -1;
+    let status = unsafe { (api.JNI_GetCreatedJavaVMs)(ptr::null_mut(), 0, &mut n_vms) };
+    assert_eq!(status, JNI_OK);
+    assert_eq!(n_vms, 0);
+}
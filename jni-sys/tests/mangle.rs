@@ -0,0 +1,117 @@
+use jni_sys::descriptor::MethodDescriptor;
+use jni_sys::mangle::{demangle, DemangleErrorKind, Symbol};
+
+#[test]
+fn mangle_names() {
+    assert_eq!(
+        Symbol::short("com/example/Foo", "bar").to_string(),
+        "Java_com_example_Foo_bar"
+    );
+    assert_eq!(
+        Symbol::short("com.example.Foo$Inner", "get_value").to_string(),
+        "Java_com_example_Foo_00024Inner_get_1value"
+    );
+
+    let descriptor = MethodDescriptor::parse("(ILjava/lang/String;[[J)V").unwrap();
+    let symbol = Symbol::long("pkg/Cls", "f", &descriptor);
+    assert!(symbol.is_long());
+    assert_eq!(
+        symbol.to_string(),
+        "Java_pkg_Cls_f__ILjava_lang_String_2_3_3J"
+    );
+    assert_eq!(symbol.to_short().to_string(), "Java_pkg_Cls_f");
+
+    let descriptor = MethodDescriptor::parse("()V").unwrap();
+    assert_eq!(
+        Symbol::new("Foo", "bar", Some(&descriptor)).to_string(),
+        "Java_Foo_bar__"
+    );
+    assert_eq!(Symbol::new("Foo", "bar", None).to_string(), "Java_Foo_bar");
+}
+
+#[test]
+fn mangle_unicode() {
+    assert_eq!(
+        Symbol::short("Ünï", "naïve").to_string(),
+        "Java__000dcn_000ef_na_000efve"
+    );
+    // Characters outside the BMP are escaped as a surrogate pair
+    assert_eq!(
+        Symbol::short("a/B", "x\u{1F600}").to_string(),
+        "Java_a_B_x_0d83d_0de00"
+    );
+}
+
+#[test]
+fn demangle_names() {
+    let symbol = demangle("Java_com_example_Foo_00024Inner_get_1value").unwrap();
+    assert_eq!(symbol.class(), "com/example/Foo$Inner");
+    assert_eq!(symbol.method(), "get_value");
+    assert!(symbol.params().is_none());
+
+    let symbol = demangle("Java_pkg_Cls_f__ILjava_lang_String_2_3_3J").unwrap();
+    assert_eq!(symbol.class(), "pkg/Cls");
+    assert_eq!(symbol.method(), "f");
+    assert_eq!(symbol.params().unwrap(), "ILjava/lang/String;[[J");
+
+    // `__` followed by an escape that can't start a descriptor is a separator and an escape
+    let symbol = demangle("Java_Foo__1bar").unwrap();
+    assert_eq!(symbol.class(), "Foo");
+    assert_eq!(symbol.method(), "_bar");
+    assert!(symbol.params().is_none());
+
+    let symbol = demangle("Java_Foo_bar___3I").unwrap();
+    assert_eq!(symbol.method(), "bar");
+    assert_eq!(symbol.params().unwrap(), "[I");
+
+    let symbol = demangle("Java_Foo_bar__").unwrap();
+    assert_eq!(symbol.params().unwrap(), "");
+}
+
+#[test]
+fn round_trip() {
+    let cases = [
+        ("com/example/Foo", "bar", None),
+        (
+            "org/ünïcödé/Klasse",
+            "méthode_naïve",
+            Some("(Ljava/lang/String;)V"),
+        ),
+        ("日本/クラス", "メソッド", Some("([Ljava/lang/Object;IJ)[B")),
+        ("emoji/\u{1F600}", "wave\u{1F44B}_hand", Some("()Z")),
+        ("a/_b/c_", "$init", Some("(Lp/Q$R;)V")),
+    ];
+    for (class, method, descriptor) in cases {
+        let descriptor = descriptor.map(|descriptor| MethodDescriptor::parse(descriptor).unwrap());
+        let mangled = Symbol::new(class, method, descriptor.as_ref()).to_string();
+        assert!(mangled.is_ascii(), "{mangled}");
+
+        let symbol = demangle(&mangled).unwrap();
+        assert_eq!(symbol.class().to_string(), class, "{mangled}");
+        assert_eq!(symbol.method().to_string(), method, "{mangled}");
+        assert_eq!(
+            symbol.params().map(|params| params.to_string()),
+            descriptor.map(|descriptor| descriptor.params_descriptor().to_string()),
+            "{mangled}"
+        );
+    }
+}
+
+#[test]
+fn demangle_errors() {
+    let cases: &[(&str, usize, DemangleErrorKind)] = &[
+        ("Jav_Foo_bar", 0, DemangleErrorKind::MissingPrefix),
+        ("Java_Foo", 8, DemangleErrorKind::MissingMethod),
+        ("Java_Foo_", 9, DemangleErrorKind::EmptyName),
+        ("Java__Foo", 5, DemangleErrorKind::EmptyName),
+        ("Java_Foo_b$r", 10, DemangleErrorKind::InvalidChar('$')),
+        ("Java_Foo_b_0zz", 10, DemangleErrorKind::InvalidEscape),
+        ("Java_Foo_b_00e", 10, DemangleErrorKind::InvalidEscape),
+        ("Java_Foo_x_0d83d", 10, DemangleErrorKind::UnpairedSurrogate),
+        ("Java_Foo_x_0de00", 10, DemangleErrorKind::UnpairedSurrogate),
+    ];
+    for (symbol, offset, kind) in cases {
+        let err = demangle(symbol).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (*offset, *kind), "{symbol}");
+    }
+}
@@ -0,0 +1,68 @@
+use jni_sys::descriptor::{JavaType, MethodDescriptor, TypeKind};
+use jni_sys::*;
+use std::ffi::CStr;
+
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+struct jpath(jobject);
+
+impl JavaType for jpath {
+    const DESCRIPTOR: &'static str = "Ljava/nio/file/Path;";
+}
+
+const SIGNATURE: &CStr = method_descriptor!(
+    unsafe extern "system" fn(*mut JNIEnv, jclass, jstring, jintArray, jlong) -> jboolean
+);
+
+#[test]
+fn java_type_descriptors() {
+    assert_eq!(jboolean::DESCRIPTOR, "Z");
+    assert_eq!(jchar::DESCRIPTOR, "C");
+    assert_eq!(jdouble::DESCRIPTOR, "D");
+    assert_eq!(jobject::DESCRIPTOR, "Ljava/lang/Object;");
+    // The array and class aliases are all `jobject`
+    assert_eq!(jintArray::DESCRIPTOR, "Ljava/lang/Object;");
+}
+
+#[test]
+fn method_descriptors() {
+    assert_eq!(SIGNATURE.to_bytes(), b"(Ljava/lang/String;[IJ)Z");
+
+    let descriptor = method_descriptor!(extern "system" fn(*mut JNIEnv, jobject));
+    assert_eq!(descriptor.to_bytes(), b"()V");
+
+    let descriptor = method_descriptor!(
+        extern "system" fn(*mut JNIEnv, jobject, jpath, jobjectArray, jfloat, jthrowable) -> jclass
+    );
+    assert_eq!(
+        descriptor.to_bytes(),
+        b"(Ljava/nio/file/Path;[Ljava/lang/Object;FLjava/lang/Throwable;)Ljava/lang/Class;"
+    );
+
+    let parsed = MethodDescriptor::parse(descriptor.to_str().unwrap()).unwrap();
+    assert_eq!(parsed.param_count(), 4);
+    assert_eq!(parsed.return_kind(), TypeKind::Object);
+}
+
+#[test]
+fn native_method_registration() {
+    unsafe extern "system" fn is_valid(
+        _env: *mut JNIEnv,
+        _class: jclass,
+        _name: jstring,
+        _ids: jintArray,
+        _stamp: jlong,
+    ) -> jboolean {
+        JNI_TRUE
+    }
+
+    let method = JNINativeMethod {
+        name: b"isValid\0".as_ptr() as *mut _,
+        signature: SIGNATURE.as_ptr() as *mut _,
+        fnPtr: is_valid as *mut _,
+    };
+    assert_eq!(
+        unsafe { CStr::from_ptr(method.signature) }.to_bytes(),
+        b"(Ljava/lang/String;[IJ)Z"
+    );
+}
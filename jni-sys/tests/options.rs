@@ -0,0 +1,327 @@
+#![cfg(feature = "std")]
+
+use jni_sys::options::{
+    split_argfile, split_options, JavaVmOptions, Launch, OptionsError, OptionsParser, ParsedArgs,
+};
+use jni_sys::JNI_VERSION_1_8;
+use std::ffi::CStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Creates an empty directory for a test under Cargo's temporary directory
+fn scratch(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("options")
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn parse(args: &[&str]) -> Result<ParsedArgs, OptionsError> {
+    OptionsParser::new().parse(args.iter().copied())
+}
+
+fn options(args: &[&str]) -> Vec<String> {
+    parse(args).unwrap().options
+}
+
+#[test]
+fn split_options_corpus() {
+    let corpus: &[(&str, &[&str])] = &[
+        ("", &[]),
+        ("   \t\n ", &[]),
+        ("-Xmx1g", &["-Xmx1g"]),
+        ("  -Xmx1g   -ea ", &["-Xmx1g", "-ea"]),
+        ("-Dname='a b'", &["-Dname=a b"]),
+        (r#"-Dname="a b"c"#, &["-Dname=a bc"]),
+        (r#""-Dquoted=it's""#, &["-Dquoted=it's"]),
+        (r#"'say "hi"'"#, &[r#"say "hi""#]),
+        ("''", &[""]),
+        (r#"a""b"#, &["ab"]),
+        (r"-Dpath=C:\dir\file", &[r"-Dpath=C:\dir\file"]),
+        ("-Da=1\t-Db=2\n-Dc=3", &["-Da=1", "-Db=2", "-Dc=3"]),
+    ];
+    for (input, expected) in corpus {
+        assert_eq!(split_options(input).unwrap(), *expected, "{input:?}");
+    }
+
+    for input in ["'unclosed", r#"-Dx="a b"#, r#"'a' "b"#] {
+        assert!(
+            matches!(split_options(input), Err(OptionsError::UnmatchedQuote(_))),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn split_argfile_corpus() {
+    let corpus: &[(&str, &[&str])] = &[
+        ("", &[]),
+        (
+            "-Xmx1g -ea\n-cp lib.jar\nMain",
+            &["-Xmx1g", "-ea", "-cp", "lib.jar", "Main"],
+        ),
+        ("-cp\r\nlib.jar\r\n", &["-cp", "lib.jar"]),
+        ("# a comment\n-ea # trailing comment\n", &["-ea"]),
+        // Checked against `java @argfile` on JDK 17
+        ("-Dx=a#b\nc#d e\n-Dy=1 # trailing\n", &["-Dy=1"]),
+        (
+            "\"q#r\" s#t\n\"u\"v#w\nx\nf\"g\"#h\ni",
+            &["q#r", "ux", "fgi"],
+        ),
+        (
+            "\"u\"v#w\n\n\"a\"#\n\"b\"#\nc\nz #x\n\"e\"#f",
+            &["uabc", "z"],
+        ),
+        ("\"-Dhash=a#b\"", &["-Dhash=a#b"]),
+        (r#"-Dname="a b""#, &["-Dname=a b"]),
+        (r#""tab\there""#, &["tab\there"]),
+        (r#""new\nline""#, &["new\nline"]),
+        (r#""quote\"d" 'single\'s'"#, &["quote\"d", "single's"]),
+        (r"C:\dir\file", &[r"C:\dir\file"]),
+        (r#""C:\\dir\\file""#, &[r"C:\dir\file"]),
+        (
+            "-cp \"lib/cool/app/jars;\\\n    lib/another app/jars\"",
+            &["-cp", "lib/cool/app/jars;lib/another app/jars"],
+        ),
+        ("\"unclosed quote\nnext", &["unclosed quote", "next"]),
+        ("\"\"", &[""]),
+        ("a\u{c}b", &["a", "b"]),
+    ];
+    for (input, expected) in corpus {
+        assert_eq!(split_argfile(input), *expected, "{input:?}");
+    }
+}
+
+#[test]
+fn class_path_options() {
+    for args in [
+        &["-cp", "a.jar:b.jar"][..],
+        &["-classpath", "a.jar:b.jar"],
+        &["--class-path", "a.jar:b.jar"],
+        &["--class-path=a.jar:b.jar"],
+    ] {
+        assert_eq!(options(args), ["-Djava.class.path=a.jar:b.jar"], "{args:?}");
+    }
+    assert!(matches!(
+        parse(&["-cp"]),
+        Err(OptionsError::MissingValue(option)) if option == "-cp"
+    ));
+}
+
+#[test]
+fn module_options() {
+    assert_eq!(
+        options(&[
+            "--add-opens",
+            "java.base/java.lang=ALL-UNNAMED",
+            "--add-opens=java.base/java.util=ALL-UNNAMED",
+            "--add-exports",
+            "java.base/sun.nio.ch=ALL-UNNAMED",
+            "--add-reads",
+            "m=ALL-UNNAMED",
+            "--add-modules",
+            "ALL-SYSTEM",
+            "-p",
+            "mods",
+            "--module-path=more-mods",
+            "--patch-module",
+            "m=patch",
+            "--enable-native-access",
+            "ALL-UNNAMED",
+        ]),
+        [
+            "--add-opens=java.base/java.lang=ALL-UNNAMED",
+            "--add-opens=java.base/java.util=ALL-UNNAMED",
+            "--add-exports=java.base/sun.nio.ch=ALL-UNNAMED",
+            "--add-reads=m=ALL-UNNAMED",
+            "--add-modules=ALL-SYSTEM",
+            "--module-path=mods",
+            "--module-path=more-mods",
+            "--patch-module=m=patch",
+            "--enable-native-access=ALL-UNNAMED",
+        ]
+    );
+}
+
+#[test]
+fn main_class_and_args() {
+    assert_eq!(
+        parse(&["-Xmx1g", "-ea", "com.example.Main", "-cp", "@arg", "x"]).unwrap(),
+        ParsedArgs {
+            options: vec!["-Xmx1g".into(), "-ea".into()],
+            launch: Some(Launch::MainClass("com.example.Main".into())),
+            args: vec!["-cp".into(), "@arg".into(), "x".into()],
+        }
+    );
+    assert_eq!(
+        parse(&["-cp", "lib.jar", "-jar", "app.jar", "--verbose"]).unwrap(),
+        ParsedArgs {
+            options: vec![
+                "-Djava.class.path=lib.jar".into(),
+                "-Djava.class.path=app.jar".into()
+            ],
+            launch: Some(Launch::Jar("app.jar".into())),
+            args: vec!["--verbose".into()],
+        }
+    );
+    assert_eq!(parse(&["-ea"]).unwrap().launch, None);
+}
+
+#[test]
+fn launcher_only_options() {
+    for option in ["-version", "--help", "-m", "--module=m/Main", "--dry-run"] {
+        assert!(
+            matches!(parse(&[option]), Err(OptionsError::LauncherOnly(arg)) if arg == option),
+            "{option}"
+        );
+    }
+    // Only the exact launcher options are rejected
+    assert_eq!(options(&["-Xmx1g", "-dsa"]), ["-Xmx1g", "-dsa"]);
+}
+
+#[test]
+fn jdk_java_options() {
+    let parser = OptionsParser::new().jdk_java_options("-Xmx1g '-Dname=a b' -cp env.jar");
+    assert_eq!(
+        parser.parse(["-ea", "Main"]).unwrap(),
+        ParsedArgs {
+            options: vec![
+                "-Xmx1g".into(),
+                "-Dname=a b".into(),
+                "-Djava.class.path=env.jar".into(),
+                "-ea".into()
+            ],
+            launch: Some(Launch::MainClass("Main".into())),
+            args: vec![],
+        }
+    );
+
+    for (options, rejected) in [
+        ("-jar app.jar", "-jar"),
+        ("Main", "Main"),
+        ("-version", "-version"),
+    ] {
+        let err = OptionsParser::new()
+            .jdk_java_options(options)
+            .parse(["Other"])
+            .unwrap_err();
+        assert!(
+            matches!(&err, OptionsError::NotAllowedInEnv(arg) if arg == rejected),
+            "{err:?}"
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("`{rejected}` is not allowed in JDK_JAVA_OPTIONS")
+        );
+    }
+    assert!(matches!(
+        OptionsParser::new()
+            .jdk_java_options("'-Da")
+            .parse(["Main"]),
+        Err(OptionsError::UnmatchedQuote(_))
+    ));
+}
+
+#[test]
+fn argfiles() {
+    let dir = scratch("argfiles");
+    let options_file = dir.join("options");
+    fs::write(
+        &options_file,
+        "# VM options\n-Xmx1g\n-cp \"lib/a b.jar\"\n--add-opens\n",
+    )
+    .unwrap();
+    let value_file = dir.join("value");
+    fs::write(
+        &value_file,
+        "java.base/java.lang=ALL-UNNAMED Main app-arg\n",
+    )
+    .unwrap();
+    let options_arg = format!("@{}", options_file.display());
+    let value_arg = format!("@{}", value_file.display());
+
+    assert_eq!(
+        parse(&[&options_arg, &value_arg, "@after-main"]).unwrap(),
+        ParsedArgs {
+            options: vec![
+                "-Xmx1g".into(),
+                "-Djava.class.path=lib/a b.jar".into(),
+                "--add-opens=java.base/java.lang=ALL-UNNAMED".into(),
+            ],
+            launch: Some(Launch::MainClass("Main".into())),
+            args: vec!["app-arg".into(), "@after-main".into()],
+        }
+    );
+
+    // Argument files aren't expanded recursively
+    let nested_file = dir.join("nested");
+    fs::write(&nested_file, format!("-ea {options_arg}")).unwrap();
+    assert_eq!(
+        parse(&[&format!("@{}", nested_file.display())]).unwrap(),
+        ParsedArgs {
+            options: vec!["-ea".into()],
+            launch: Some(Launch::MainClass(options_arg.clone())),
+            args: vec![],
+        }
+    );
+
+    // `@@` escapes the `@`, and `--disable-@files` stops expansion
+    assert_eq!(
+        parse(&["@@literal"]).unwrap().launch,
+        Some(Launch::MainClass("@literal".into()))
+    );
+    let unexpanded = Some(Launch::MainClass(options_arg.clone()));
+    assert_eq!(
+        parse(&["-ea", "--disable-@files", &options_arg])
+            .unwrap()
+            .launch,
+        unexpanded
+    );
+    assert_eq!(
+        OptionsParser::new()
+            .expand_argfiles(false)
+            .parse([options_arg.as_str()])
+            .unwrap()
+            .launch,
+        unexpanded
+    );
+
+    // Argument files may be named in JDK_JAVA_OPTIONS, with the same restrictions
+    let parsed = OptionsParser::new()
+        .jdk_java_options(format!("'{options_arg}'"))
+        .parse(["java.base/java.util=ALL-UNNAMED", "Main"])
+        .unwrap();
+    assert_eq!(
+        parsed.options.last().unwrap(),
+        "--add-opens=java.base/java.util=ALL-UNNAMED"
+    );
+
+    let missing = dir.join("missing");
+    let err = parse(&[&format!("@{}", missing.display())]).unwrap_err();
+    assert!(matches!(&err, OptionsError::Argfile { path, .. } if *path == missing));
+}
+
+#[test]
+fn java_vm_options() {
+    let parsed = parse(&["-Xmx1g", "-cp", "app.jar", "Main"]).unwrap();
+    let mut options = JavaVmOptions::new(parsed.options).unwrap();
+    let args = options.init_args(JNI_VERSION_1_8, false);
+    assert_eq!(args.version, JNI_VERSION_1_8);
+    assert_eq!(args.nOptions, 2);
+    let strings: Vec<_> = (0..args.nOptions as usize)
+        .map(|i| unsafe { CStr::from_ptr((*args.options.add(i)).optionString) })
+        .collect();
+    assert_eq!(
+        strings,
+        [
+            CStr::from_bytes_with_nul(b"-Xmx1g\0").unwrap(),
+            CStr::from_bytes_with_nul(b"-Djava.class.path=app.jar\0").unwrap()
+        ]
+    );
+    assert_eq!(options.as_slice().len(), 2);
+    assert_eq!(options.strings().len(), 2);
+
+    assert!(JavaVmOptions::new(["-Dnul=\0"]).is_err());
+}
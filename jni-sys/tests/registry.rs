@@ -0,0 +1,27 @@
+use jni_sys::registry::{
+    java_vm, register_java_vm, unregister_java_vm, NotRegistered, RegisteredVm,
+};
+use jni_sys::{JavaVM, JNI_VERSION_1_6, JNI_VERSION_1_8};
+use std::ptr::{self, NonNull};
+
+#[test]
+fn register_and_unregister() {
+    assert_eq!(java_vm(), Err(NotRegistered));
+    assert_eq!(unregister_java_vm(), None);
+    assert_eq!(NotRegistered.to_string(), "no Java VM has been registered");
+
+    let vm = NonNull::<JavaVM>::dangling().as_ptr();
+    register_java_vm(vm, JNI_VERSION_1_6);
+    register_java_vm(vm, JNI_VERSION_1_8);
+    let registered = RegisteredVm {
+        vm,
+        version: JNI_VERSION_1_8,
+    };
+    assert_eq!(java_vm(), Ok(registered));
+    assert_eq!(unregister_java_vm(), Some(registered));
+    assert_eq!(java_vm(), Err(NotRegistered));
+
+    register_java_vm(vm, JNI_VERSION_1_8);
+    register_java_vm(ptr::null_mut(), JNI_VERSION_1_8);
+    assert_eq!(java_vm(), Err(NotRegistered));
+}
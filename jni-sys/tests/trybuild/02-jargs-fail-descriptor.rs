@@ -0,0 +1,5 @@
+use jni_sys::jargs;
+
+pub fn main() {
+    let _args = jargs!("(Ljava/lang/String)V"; std::ptr::null_mut());
+}
@@ -0,0 +1,6 @@
+use jni_sys::{jargs, jlong};
+
+pub fn main() {
+    let wide: jlong = 1;
+    let _args = jargs!("(IZ)V"; wide, true);
+}
@@ -0,0 +1,8 @@
+use jni_sys::{jint, jni_export, jobject, JNIEnv};
+
+#[jni_export(class = "com.example.Foo")]
+extern "system" fn answer(_env: *const JNIEnv, _this: jobject) -> jint {
+    42
+}
+
+pub fn main() {}
@@ -0,0 +1,8 @@
+use jni_sys::{jni_export, jobject, JNIEnv};
+
+pub struct Handle(u64);
+
+#[jni_export(class = "com.example.Foo", overloaded = true)]
+extern "system" fn close(_env: *mut JNIEnv, _this: jobject, _handle: Handle) {}
+
+pub fn main() {}
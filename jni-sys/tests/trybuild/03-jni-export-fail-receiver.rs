@@ -0,0 +1,8 @@
+use jni_sys::{jint, jni_export, JNIEnv};
+
+#[jni_export(class = "com.example.Foo")]
+extern "system" fn answer(_env: *mut JNIEnv, _value: jint) -> jint {
+    42
+}
+
+pub fn main() {}
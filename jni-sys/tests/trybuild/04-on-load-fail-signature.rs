@@ -0,0 +1,8 @@
+use jni_sys::JavaVM;
+use std::ffi::c_void;
+
+fn on_load(_vm: *mut JavaVM, _reserved: *mut c_void) {}
+
+jni_sys::jni_on_load!(on_load);
+
+fn main() {}
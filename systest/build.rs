@@ -79,6 +79,7 @@ impl Config {
     fn test(&self) -> TestGenerator {
         let target = self.target;
         let mut test = TestGenerator::new();
+        test.cfg("ctest", None);
         let mut includes = self.java_home.join("include");
         test.include(&includes);
         includes.push(self.target.platform_dir());
@@ -93,6 +94,20 @@ impl Config {
         });
         test
     }
+
+    /// Compiles the C helpers that ctest2 can't generate
+    fn helpers(&self) {
+        let mut build = cc::Build::new();
+        let mut includes = self.java_home.join("include");
+        build.include(&includes);
+        includes.push(self.target.platform_dir());
+        build.include(&includes);
+        if cfg!(feature = "jni21") {
+            build.define("SYSTEST_JVMTI_21", None);
+        }
+        println!("cargo:rerun-if-changed=src/capabilities.c");
+        build.file("src/capabilities.c").compile("systest_helpers");
+    }
 }
 
 fn main() {
@@ -140,4 +155,32 @@ fn main() {
             )
         }) // We don't need to be able to roundtrip all possible u8 values for a jboolean, since only 0 are 1 are considered valid.
         .export("jni");
+
+    cfg.test()
+        .header("jvmti.h")
+        .skip_field(|s, _| s == "jvmtiInterface_1_") // ctest2 isn't able to test this union
+        // Virtual thread events
+        .skip_struct(|s| s == "jvmtiEventCallbacks" && !cfg!(feature = "jni21"))
+        .skip_type(|s| {
+            matches!(
+                s,
+                "jvmtiEventVirtualThreadStart" | "jvmtiEventVirtualThreadEnd"
+            ) && !cfg!(feature = "jni21")
+                || s == "jniNativeInterface" // Checked as `JNINativeInterface_`
+        })
+        .skip_const(|konst| match konst {
+            "JVMTI_VERSION_19" => !cfg!(feature = "jni19"),
+            "JVMTI_VERSION_21"
+            | "JVMTI_EVENT_VIRTUAL_THREAD_START"
+            | "JVMTI_EVENT_VIRTUAL_THREAD_END"
+            | "JVMTI_MAX_EVENT_TYPE_VAL"
+            | "JVMTI_ERROR_UNSUPPORTED_OPERATION" => !cfg!(feature = "jni21"),
+            _ => false,
+        })
+        // The C enums are bound as `jint`, and the rest are pointers
+        .skip_signededness(|s| s != "jlocation")
+        .skip_roundtrip(|s| s == "jvmtiInterface_1_")
+        .generate("../jni-sys/src/jvmti.rs", "jvmti.rs");
+
+    cfg.helpers();
 }
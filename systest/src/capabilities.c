@@ -0,0 +1,63 @@
+/* Sets the bitfields of `jvmtiCapabilities` by name, to check the bit of each capability */
+
+#include <string.h>
+
+#include <jvmti.h>
+
+#define CAPABILITY(field)                               \
+    if (strcmp(name, #field) == 0) {                    \
+        memset(capabilities, 0, sizeof(*capabilities)); \
+        capabilities->field = 1;                        \
+        return 1;                                       \
+    }
+
+int systest_set_capability(const char *name, jvmtiCapabilities *capabilities) {
+    CAPABILITY(can_tag_objects)
+    CAPABILITY(can_generate_field_modification_events)
+    CAPABILITY(can_generate_field_access_events)
+    CAPABILITY(can_get_bytecodes)
+    CAPABILITY(can_get_synthetic_attribute)
+    CAPABILITY(can_get_owned_monitor_info)
+    CAPABILITY(can_get_current_contended_monitor)
+    CAPABILITY(can_get_monitor_info)
+    CAPABILITY(can_pop_frame)
+    CAPABILITY(can_redefine_classes)
+    CAPABILITY(can_signal_thread)
+    CAPABILITY(can_get_source_file_name)
+    CAPABILITY(can_get_line_numbers)
+    CAPABILITY(can_get_source_debug_extension)
+    CAPABILITY(can_access_local_variables)
+    CAPABILITY(can_maintain_original_method_order)
+    CAPABILITY(can_generate_single_step_events)
+    CAPABILITY(can_generate_exception_events)
+    CAPABILITY(can_generate_frame_pop_events)
+    CAPABILITY(can_generate_breakpoint_events)
+    CAPABILITY(can_suspend)
+    CAPABILITY(can_redefine_any_class)
+    CAPABILITY(can_get_current_thread_cpu_time)
+    CAPABILITY(can_get_thread_cpu_time)
+    CAPABILITY(can_generate_method_entry_events)
+    CAPABILITY(can_generate_method_exit_events)
+    CAPABILITY(can_generate_all_class_hook_events)
+    CAPABILITY(can_generate_compiled_method_load_events)
+    CAPABILITY(can_generate_monitor_events)
+    CAPABILITY(can_generate_vm_object_alloc_events)
+    CAPABILITY(can_generate_native_method_bind_events)
+    CAPABILITY(can_generate_garbage_collection_events)
+    CAPABILITY(can_generate_object_free_events)
+    CAPABILITY(can_force_early_return)
+    CAPABILITY(can_get_owned_monitor_stack_depth_info)
+    CAPABILITY(can_get_constant_pool)
+    CAPABILITY(can_set_native_method_prefix)
+    CAPABILITY(can_retransform_classes)
+    CAPABILITY(can_retransform_any_class)
+    CAPABILITY(can_generate_resource_exhaustion_heap_events)
+    CAPABILITY(can_generate_resource_exhaustion_threads_events)
+    CAPABILITY(can_generate_early_vmstart)
+    CAPABILITY(can_generate_early_class_hook_events)
+    CAPABILITY(can_generate_sampled_object_alloc_events)
+#ifdef SYSTEST_JVMTI_21
+    CAPABILITY(can_support_virtual_threads)
+#endif
+    return 0;
+}
@@ -1,10 +1,10 @@
 #![allow(function_casts_as_integer)]
 
 macro_rules! tests {
-    ($name:ident for $($lib:ident)and*) => {
+    ($name:ident for $($lib:ident $(:: $sub:ident)*)and*) => {
         mod $name {
             $(
-            use $lib::*;
+            use $lib $(:: $sub)* ::*;
             )*
             include!(concat!(env!("OUT_DIR"), "/", stringify!($name), ".rs"));
             pub fn exec() {
@@ -22,7 +22,57 @@ macro_rules! tests {
 }
 
 tests!(jni for jni_sys);
+tests!(jvmti for jni_sys and jni_sys::jvmti);
+
+/// Checks the bit of each capability against the bitfields of `jvmtiCapabilities` in C
+mod capabilities {
+    use jni_sys::jvmti::capabilities::Capability;
+    use jni_sys::jvmti::jvmtiCapabilities;
+    use std::ffi::CString;
+    use std::os::raw::{c_char, c_int};
+
+    extern "C" {
+        fn systest_set_capability(
+            name: *const c_char,
+            capabilities: *mut jvmtiCapabilities,
+        ) -> c_int;
+    }
+
+    pub fn exec() {
+        eprintln!("TESTS FOR 'capabilities'");
+        let mut failed = false;
+        for &capability in Capability::ALL {
+            let name = CString::new(capability.name()).unwrap();
+            let mut c = jvmtiCapabilities::new();
+            if unsafe { systest_set_capability(name.as_ptr(), &mut c) } == 0 {
+                if capability == Capability::CanSupportVirtualThreads && !cfg!(feature = "jni21") {
+                    continue;
+                }
+                eprintln!("unknown capability {capability}");
+                failed = true;
+                continue;
+            }
+            let rust = jvmtiCapabilities::from(capability);
+            if c != rust {
+                eprintln!("bad capability {capability}: rust: {rust:?} != c {c:?}");
+                failed = true;
+            }
+        }
+        if failed {
+            panic!("some tests failed");
+        }
+        eprintln!("PASSED {} tests", Capability::ALL.len());
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn capabilities() {
+    capabilities::exec();
+}
 
 fn main() {
     jni::exec();
+    jvmti::exec();
+    capabilities::exec();
 }